@@ -0,0 +1,115 @@
+//! Async generation for tokio-based tools (`tokio` feature).
+//!
+//! The whole run, detection through the manifest write, goes to tokio's
+//! blocking pool and takes the same path as the synchronous API, so the
+//! configured [`FileSystem`](crate::FileSystem) and every output option
+//! apply unchanged.
+
+use crate::{GenerateOptions, GenerationSummary, LatestJson, LatestJsonError, Result};
+use std::{io, path::Path};
+
+impl GenerateOptions {
+    /// Async version of [`generate`](Self::generate).
+    pub async fn generate_async(&self) -> Result<LatestJson> {
+        self.generate_with_summary_async()
+            .await
+            .map(|(manifest, _)| manifest)
+    }
+
+    /// Async version of [`generate_with_summary`](Self::generate_with_summary).
+    pub async fn generate_with_summary_async(&self) -> Result<(LatestJson, GenerationSummary)> {
+        let options = self.clone();
+        tokio::task::spawn_blocking(move || options.generate_with_summary())
+            .await
+            .map_err(|e| LatestJsonError::Io(io::Error::other(e)))?
+    }
+}
+
+/// Async version of [`generate_latest_json`](crate::generate_latest_json).
+pub async fn generate_latest_json_async(
+    bundle_dir: &Path,
+    public_key: &str,
+    download_url_base: &str,
+    notes: &str,
+    output_path: &Path,
+) -> Result<GenerationSummary> {
+    GenerateOptions::new(download_url_base)
+        .notes(notes)
+        .bundle_dir(bundle_dir)
+        .public_key(public_key)
+        .output(output_path)
+        .generate_with_summary_async()
+        .await
+        .map(|(_, summary)| summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{make_temp_dir, write_file};
+
+    #[test]
+    fn test_generate_latest_json_async_writes_manifest() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("bundle");
+        write_file(&bundle_dir.join("app_1.0.0_amd64.AppImage"), "appimage");
+        #[cfg(feature = "verify-signature")]
+        let (public_key, signature) = crate::signature::tests::tauri_sign(b"appimage");
+        #[cfg(not(feature = "verify-signature"))]
+        let (public_key, signature) = ("pubkey".to_string(), "sig".to_string());
+        write_file(&bundle_dir.join("app_1.0.0_amd64.AppImage.sig"), &signature);
+        let output_path = dir.join("dist").join("latest.json");
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let manifest = runtime
+            .block_on(
+                GenerateOptions::new("https://example.com/downloads")
+                    .bundle_dir(&bundle_dir)
+                    .public_key(public_key)
+                    .version("1.0.0")
+                    .output(&output_path)
+                    .generate_async(),
+            )
+            .unwrap();
+
+        assert_eq!(manifest.platforms["linux-x86_64"].signature, signature);
+        let written: LatestJson =
+            serde_json::from_str(&std::fs::read_to_string(&output_path).unwrap()).unwrap();
+        assert_eq!(written, manifest);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_generate_async_writes_through_configured_filesystem() {
+        let mock = crate::MockFs::new()
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage.sig", "sig")
+            .with_file("/out/latest.json", "previous");
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let manifest = runtime
+            .block_on(
+                GenerateOptions::new("https://example.com/downloads")
+                    .bundle_dir("/bundle")
+                    .public_key("pubkey")
+                    .version("1.0.0")
+                    .output("/out/latest.json")
+                    .verify(crate::VerifyMode::PresenceOnly)
+                    .backup(true)
+                    .filesystem(mock.clone())
+                    .generate_async(),
+            )
+            .unwrap();
+
+        let written: LatestJson =
+            serde_json::from_slice(&mock.get(Path::new("/out/latest.json")).unwrap()).unwrap();
+        assert_eq!(written, manifest);
+        assert_eq!(
+            mock.get(Path::new("/out/latest.json.bak")).unwrap(),
+            b"previous"
+        );
+    }
+}
@@ -1,37 +1,643 @@
-use clap::{CommandFactory, Parser};
+use chrono::{DateTime, Utc};
+use clap::{Args, Parser, Subcommand};
 use colored::*;
+use std::path::PathBuf;
+use tauri_latest_json::{
+    GenerateOptions, LatestJsonError, ManifestFormat, OutputMode, OutputTarget, PackageJsonLookup,
+    PubDateFormat, VerifyMode, VersionSource, WindowsInstaller,
+};
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     /// The base URL where installers are hosted (e.g., https://github.com/user/repo/releases/download/v1.0.0)
     download_url_base: Option<String>,
 
     /// Release notes for this update
     #[arg(trailing_var_arg = true)]
     notes: Vec<String>,
+
+    /// Print debug details about detection and matching
+    #[arg(short, long, global = true)]
+    verbose: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Generate latest.json from the built bundle directory
+    Generate(Box<GenerateArgs>),
+    /// List installers, their platform keys, and signatures without generating anything
+    Inspect {
+        /// Bundle directory to inspect (auto-detected when omitted)
+        #[arg(long)]
+        bundle_dir: Option<PathBuf>,
+    },
+    /// Check that an existing latest.json has all required fields
+    Verify(VerifyArgs),
+    /// Sign installers that have no .sig yet
+    #[cfg(feature = "sign")]
+    Sign {
+        /// Bundle directory to sign (auto-detected when omitted)
+        #[arg(long)]
+        bundle_dir: Option<PathBuf>,
+
+        /// Private key (defaults to TAURI_SIGNING_PRIVATE_KEY)
+        #[arg(long)]
+        private_key: Option<String>,
+
+        /// Private key password (defaults to TAURI_SIGNING_PRIVATE_KEY_PASSWORD)
+        #[arg(long)]
+        password: Option<String>,
+    },
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    /// Path to the latest.json to check
+    path: PathBuf,
+
+    /// Also verify each signature against the matching installer in the bundle
+    #[cfg(feature = "verify-signature")]
+    #[arg(long)]
+    check_signatures: bool,
+
+    /// Bundle directory for --check-signatures (auto-detected when omitted)
+    #[cfg(feature = "verify-signature")]
+    #[arg(long, requires = "check_signatures")]
+    bundle_dir: Option<PathBuf>,
+
+    /// Path to tauri.conf.json for --check-signatures (auto-detected when omitted)
+    #[cfg(feature = "verify-signature")]
+    #[arg(long, requires = "check_signatures")]
+    config: Option<PathBuf>,
+}
+
+impl VerifyArgs {
+    fn run(&self) -> Result<(), LatestJsonError> {
+        tauri_latest_json::check_latest_json(&self.path)?;
+        #[cfg(feature = "verify-signature")]
+        if self.check_signatures {
+            let failures = tauri_latest_json::verify_latest_json_auto(
+                &self.path,
+                self.bundle_dir.as_deref(),
+                self.config.as_deref(),
+            )?;
+            if !failures.is_empty() {
+                for failure in &failures {
+                    eprintln!(
+                        "{} {}: {}",
+                        "mismatch:".red().bold(),
+                        failure.platform,
+                        failure.reason
+                    );
+                }
+                std::process::exit(3);
+            }
+        }
+        println!("{} {} is valid", "✔".green().bold(), self.path.display());
+        Ok(())
+    }
+}
+
+#[derive(Args, Debug)]
+struct GenerateArgs {
+    /// The base URL where installers are hosted
+    #[arg(long)]
+    url: String,
+
+    /// Release notes for this update
+    #[arg(long)]
+    notes: Option<String>,
+
+    /// Read the release notes from this file
+    #[arg(long, value_name = "PATH", conflicts_with = "notes")]
+    notes_file: Option<PathBuf>,
+
+    /// Use this version's section of a Keep a Changelog file as the notes
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["notes", "notes_file"])]
+    notes_from_changelog: Option<PathBuf>,
+
+    /// Where to write the manifest
+    #[arg(long, default_value = "latest.json")]
+    output: PathBuf,
+
+    /// Write the manifest to stdout instead of --output (logs stay on stderr)
+    #[arg(long, conflicts_with_all = ["output", "merge", "backup"])]
+    stdout: bool,
+
+    /// Write one manifest per platform next to --output, named by PATTERN ({{platform}}, {{os}}, {{arch}})
+    #[arg(long, value_name = "PATTERN", num_args = 0..=1, default_missing_value = tauri_latest_json::DEFAULT_PER_PLATFORM_FILE_NAME, conflicts_with_all = ["stdout", "merge"])]
+    per_platform: Option<String>,
+
+    /// Write pre-release versions (e.g. 1.2.0-beta.3) to latest-<channel>.json instead
+    #[arg(long)]
+    channel_aware: bool,
+
+    /// Manifest path for one channel, e.g. beta=dist/beta.json (repeatable; implies --channel-aware)
+    #[arg(long = "channel-output", value_name = "CHANNEL=PATH", value_parser = parse_channel_output)]
+    channel_outputs: Vec<(String, PathBuf)>,
+
+    /// Path to tauri.conf.json (auto-detected when omitted)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Build profile whose bundle to use, e.g. debug (default: release, then debug)
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Only treat files with these extensions as installers, e.g. msi,app.tar.gz,AppImage
+    #[arg(long, value_name = "EXT", value_delimiter = ',')]
+    extensions: Vec<String>,
+
+    /// Only use installers whose file name starts with this product name, when several apps share the bundle dir
+    #[arg(long, value_name = "NAME")]
+    app_name: Option<String>,
+
+    /// Extension of signature files next to installers, e.g. minisig (default sig)
+    #[arg(long, value_name = "EXT")]
+    signature_extension: Option<String>,
+
+    /// How many directory levels below the bundle dir to search for installers (default 4)
+    #[arg(long, value_name = "DEPTH")]
+    max_depth: Option<usize>,
+
+    /// Fail instead of skipping installers whose names match no platform
+    #[arg(long)]
+    strict_platforms: bool,
+
+    /// Follow symlinks into other directories while searching the bundle
+    #[arg(long)]
+    follow_symlinks: bool,
+
+    /// Retry installer and signature reads this many times on transient IO errors, with backoff
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    read_retries: u32,
+
+    /// Windows installer for the updater when both exist: msi or nsis
+    #[arg(long, value_name = "KIND", default_value = "msi", value_parser = parse_windows_installer)]
+    windows_installer: WindowsInstaller,
+
+    /// Updater generation to target: v2 (installers) or v1 (.msi.zip, .nsis.zip, .AppImage.tar.gz archives)
+    #[arg(long, value_name = "FORMAT", default_value = "v2", value_parser = parse_manifest_format)]
+    manifest_format: ManifestFormat,
+
+    /// Signature checks: full (verify-signature builds), presence (default), or off
+    #[arg(long, value_name = "MODE", value_parser = parse_verify_mode)]
+    verify: Option<VerifyMode>,
+
+    /// Read the updater public key from this .pub file (e.g. ~/.tauri/myapp.key.pub)
+    #[arg(long, value_name = "PATH")]
+    public_key_file: Option<PathBuf>,
+
+    /// Version to publish instead of reading it from project files
+    #[arg(long)]
+    app_version: Option<String>,
+
+    /// Project file to read the version from first: auto, package-json, cargo-toml, or tauri-conf
+    #[arg(long, value_name = "SOURCE", default_value = "auto", value_parser = parse_version_source)]
+    version_source: VersionSource,
+
+    /// Which package.json has the version: local, nearest (walking up), or workspace-root
+    #[arg(long, value_name = "LOOKUP", default_value = "local", value_parser = parse_package_json_lookup)]
+    package_json: PackageJsonLookup,
+
+    /// Use the latest git tag when no project file has a version
+    #[arg(long)]
+    git_fallback: bool,
+
+    /// Fail listing every discovered installer without a signature, including .dmg files
+    #[arg(long)]
+    require_all_signed: bool,
+
+    /// Never spawn processes (skips --git-fallback); generation never uses the network
+    #[arg(long)]
+    offline: bool,
+
+    /// Skip installers whose file name version differs when the bundle mixes builds, instead of failing
+    #[arg(long)]
+    version_filter: bool,
+
+    /// Mark this release as critical ("critical": true) for apps that force security updates
+    #[arg(long)]
+    critical: bool,
+
+    /// Clients below this version must update; written as "minimum_version"
+    #[arg(long, value_name = "VERSION")]
+    minimum_version: Option<String>,
+
+    /// Accept versions that are not valid semver
+    #[arg(long)]
+    allow_non_semver: bool,
+
+    /// Merge into an existing manifest at --output instead of overwriting it
+    #[arg(long)]
+    merge: bool,
+
+    /// Notes for a single platform, e.g. darwin-aarch64="Re-grant permissions" (repeatable)
+    #[arg(long = "platform-notes", value_name = "PLATFORM=NOTES", value_parser = parse_platform_notes)]
+    platform_notes: Vec<(String, String)>,
+
+    /// Exact download URL for one platform, e.g. darwin-aarch64=https://mirror/App.app.tar.gz (repeatable)
+    #[arg(long = "url-override", value_name = "PLATFORM=URL", value_parser = parse_url_override)]
+    url_overrides: Vec<(String, String)>,
+
+    /// Publication date (RFC 3339) instead of the current time
+    #[arg(long, value_name = "RFC3339")]
+    pub_date: Option<DateTime<Utc>>,
+
+    /// pub_date layout: rfc3339, rfc3339-millis, or a strftime string such as %Y-%m-%dT%H:%M:%S%.6fZ
+    #[arg(long, value_name = "FORMAT", value_parser = parse_pub_date_format)]
+    pub_date_format: Option<PubDateFormat>,
+
+    /// Allow a file:// --url for local testing
+    #[arg(long)]
+    allow_file_urls: bool,
+
+    /// Add a sha256 checksum to every platform entry
+    #[arg(long)]
+    checksums: bool,
+
+    /// Add the installer size in bytes to every platform entry
+    #[arg(long)]
+    size: bool,
+
+    /// Keep each installer's path below the bundle directory in its URL (e.g. nsis/App.exe)
+    #[arg(long)]
+    preserve_relative_path: bool,
+
+    /// Fail unless these platform keys end up in the manifest (comma-separated or repeated)
+    #[arg(
+        long = "expect-platform",
+        value_name = "PLATFORM",
+        value_delimiter = ','
+    )]
+    expect_platforms: Vec<String>,
+
+    /// Only warn when an --expect-platform key is missing
+    #[arg(long, requires = "expect_platforms")]
+    allow_missing_platforms: bool,
+
+    /// Copy an existing manifest at --output to <output>.bak before overwriting it
+    #[arg(long)]
+    backup: bool,
+
+    /// Fail instead of replacing a manifest that already exists at the output path
+    #[arg(long, conflicts_with_all = ["merge", "backup"])]
+    no_overwrite: bool,
+
+    /// Skip the final check that the manifest is what the Tauri updater accepts
+    #[arg(long)]
+    no_validate: bool,
+
+    /// Write compact JSON instead of indented output
+    #[arg(long)]
+    compact: bool,
+
+    /// Also write one {target}/{arch}.json per platform here, for a dynamic updater endpoint
+    #[arg(long, value_name = "DIR")]
+    dynamic_dir: Option<PathBuf>,
+
+    /// Append version and platforms to $GITHUB_OUTPUT for later workflow steps
+    #[arg(long)]
+    github_output: bool,
+
+    /// Write a JSON report of the run (version, installers, signature status, warnings) to this path
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+
+    /// Print the manifest instead of writing it; errors still fail the run
+    #[arg(long)]
+    dry_run: bool,
+}
+
+fn parse_platform_notes(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((platform, notes)) if !platform.is_empty() => {
+            Ok((platform.to_string(), notes.to_string()))
+        }
+        _ => Err(format!("expected PLATFORM=NOTES, got '{}'", value)),
+    }
+}
+
+fn parse_url_override(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((platform, url)) if !platform.is_empty() && !url.is_empty() => {
+            Ok((platform.to_string(), url.to_string()))
+        }
+        _ => Err(format!("expected PLATFORM=URL, got '{}'", value)),
+    }
+}
+
+fn parse_channel_output(value: &str) -> Result<(String, PathBuf), String> {
+    match value.split_once('=') {
+        Some((channel, path)) if !channel.is_empty() && !path.is_empty() => {
+            Ok((channel.to_string(), PathBuf::from(path)))
+        }
+        _ => Err(format!("expected CHANNEL=PATH, got '{}'", value)),
+    }
+}
+
+fn parse_windows_installer(value: &str) -> Result<WindowsInstaller, String> {
+    match value {
+        "msi" => Ok(WindowsInstaller::Msi),
+        "nsis" => Ok(WindowsInstaller::Nsis),
+        _ => Err(format!("expected msi or nsis, got '{}'", value)),
+    }
+}
+
+fn parse_manifest_format(value: &str) -> Result<ManifestFormat, String> {
+    match value {
+        "v1" => Ok(ManifestFormat::V1),
+        "v2" => Ok(ManifestFormat::V2),
+        _ => Err(format!("expected v1 or v2, got '{}'", value)),
+    }
+}
+
+fn parse_pub_date_format(value: &str) -> Result<PubDateFormat, String> {
+    match value {
+        "rfc3339" => Ok(PubDateFormat::Rfc3339Secs),
+        "rfc3339-millis" => Ok(PubDateFormat::Rfc3339Millis),
+        _ if value.contains('%') => Ok(PubDateFormat::Custom(value.to_string())),
+        _ => Err(format!(
+            "expected rfc3339, rfc3339-millis, or a strftime layout, got '{}'",
+            value
+        )),
+    }
+}
+
+fn parse_verify_mode(value: &str) -> Result<VerifyMode, String> {
+    match value {
+        #[cfg(feature = "verify-signature")]
+        "full" => Ok(VerifyMode::Full),
+        #[cfg(not(feature = "verify-signature"))]
+        "full" => Err("full verification needs the verify-signature feature".to_string()),
+        "presence" => Ok(VerifyMode::PresenceOnly),
+        "off" => Ok(VerifyMode::Off),
+        _ => Err(format!("expected full, presence, or off, got '{}'", value)),
+    }
+}
+
+fn parse_package_json_lookup(value: &str) -> Result<PackageJsonLookup, String> {
+    match value {
+        "local" => Ok(PackageJsonLookup::Local),
+        "nearest" => Ok(PackageJsonLookup::Nearest),
+        "workspace-root" => Ok(PackageJsonLookup::WorkspaceRoot),
+        _ => Err(format!(
+            "expected local, nearest, or workspace-root, got '{}'",
+            value
+        )),
+    }
+}
+
+fn parse_version_source(value: &str) -> Result<VersionSource, String> {
+    match value {
+        "auto" => Ok(VersionSource::Auto),
+        "package-json" => Ok(VersionSource::PackageJson),
+        "cargo-toml" => Ok(VersionSource::CargoToml),
+        "tauri-conf" => Ok(VersionSource::TauriConf),
+        _ => Err(format!(
+            "expected auto, package-json, cargo-toml, or tauri-conf, got '{}'",
+            value
+        )),
+    }
+}
+
+impl GenerateArgs {
+    fn run(&self) -> Result<(), LatestJsonError> {
+        let (manifest, summary) = self.options().generate_with_summary()?;
+        if let Some(dir) = &self.dynamic_dir {
+            if self.dry_run {
+                log::info!(
+                    "dry run, not writing dynamic manifests to {}",
+                    dir.display()
+                );
+            } else {
+                tauri_latest_json::generate_dynamic_manifests(&manifest, dir)?;
+            }
+        }
+        log::info!(
+            "{} platform(s) for version {}",
+            summary.platform_keys.len(),
+            summary.version
+        );
+        for warning in &summary.warnings {
+            log::warn!("{}", warning);
+        }
+        if let Some(path) = &self.report {
+            if self.dry_run {
+                log::info!("dry run, not writing the run report to {}", path.display());
+            } else {
+                tauri_latest_json::write_report(&summary, path)?;
+            }
+        }
+        if self.github_output && !tauri_latest_json::write_github_output(&summary)? {
+            log::warn!("--github-output given but GITHUB_OUTPUT is not set");
+        }
+        Ok(())
+    }
+
+    fn options(&self) -> GenerateOptions {
+        let mut options = GenerateOptions::new(&self.url)
+            .output(&self.output)
+            .channel_aware(self.channel_aware)
+            .version_source(self.version_source)
+            .package_json_lookup(self.package_json)
+            .windows_installer_preference(self.windows_installer)
+            .manifest_format(self.manifest_format)
+            .follow_symlinks(self.follow_symlinks)
+            .read_retries(self.read_retries)
+            .strict_platforms(self.strict_platforms)
+            .strict_semver(!self.allow_non_semver)
+            .version_filter(self.version_filter)
+            .critical(self.critical)
+            .validate(!self.no_validate)
+            .git_fallback(self.git_fallback)
+            .offline(self.offline)
+            .require_all_signed(self.require_all_signed)
+            .merge_into_existing(self.merge)
+            .dry_run(self.dry_run)
+            .include_checksums(self.checksums)
+            .include_size(self.size)
+            .allow_file_urls(self.allow_file_urls)
+            .preserve_relative_path(self.preserve_relative_path)
+            .pretty(!self.compact)
+            .backup(self.backup)
+            .overwrite(!self.no_overwrite)
+            .expect_platforms(self.expect_platforms.iter().cloned())
+            .allow_missing_platforms(self.allow_missing_platforms)
+            .platform_notes(self.platform_notes.iter().cloned().collect())
+            .url_overrides(self.url_overrides.iter().cloned().collect());
+        for (channel, path) in &self.channel_outputs {
+            options = options.channel_output(channel, path);
+        }
+        if let Some(config) = &self.config {
+            options = options.tauri_conf_path(config);
+        }
+        if !self.extensions.is_empty() {
+            options = options.installer_extensions(self.extensions.iter().cloned());
+        }
+        if let Some(notes) = &self.notes {
+            options = options.notes(notes);
+        }
+        if let Some(path) = &self.notes_file {
+            options = options.notes_from_file(path);
+        }
+        if let Some(path) = &self.notes_from_changelog {
+            options = options.notes_from_changelog(path);
+        }
+        if let Some(depth) = self.max_depth {
+            options = options.max_depth(depth);
+        }
+        if self.stdout {
+            options = options.output_target(OutputTarget::Stdout);
+        }
+        if let Some(file_name) = &self.per_platform {
+            options = options.output_mode(OutputMode::PerPlatform {
+                file_name: file_name.clone(),
+            });
+        }
+        if let Some(mode) = self.verify {
+            options = options.verify(mode);
+        }
+        if let Some(app_name) = &self.app_name {
+            options = options.app_name(app_name);
+        }
+        if let Some(extension) = &self.signature_extension {
+            options = options.signature_extension(extension);
+        }
+        if let Some(profile) = &self.profile {
+            options = options.profile(profile);
+        }
+        if let Some(path) = &self.public_key_file {
+            options = options.public_key_path(path);
+        }
+        if let Some(version) = &self.minimum_version {
+            options = options.minimum_version(version);
+        }
+        if let Some(version) = &self.app_version {
+            options = options.version(version);
+        }
+        if let Some(pub_date) = self.pub_date {
+            options = options.pub_date(pub_date);
+        }
+        if let Some(format) = &self.pub_date_format {
+            options = options.pub_date_format(format.clone());
+        }
+        options
+    }
+}
+
+/// Writes library log records to stderr so stdout stays machine-readable.
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let prefix = match record.level() {
+            log::Level::Error => "error:".red().bold(),
+            log::Level::Warn => "warning:".yellow().bold(),
+            log::Level::Info => "info:".cyan(),
+            log::Level::Debug | log::Level::Trace => "debug:".dimmed(),
+        };
+        eprintln!("{} {}", prefix, record.args());
+    }
+
+    fn flush(&self) {}
+}
+
+fn init_logger(verbose: bool) {
+    let level = if verbose {
+        log::LevelFilter::Debug
+    } else {
+        log::LevelFilter::Info
+    };
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+/// Maps library errors to process exit codes so scripts can branch on them.
+fn exit_code(e: &LatestJsonError) -> i32 {
+    match e {
+        LatestJsonError::NoInstallers { .. } => 2,
+        LatestJsonError::MissingSignature { .. }
+        | LatestJsonError::EmptySignature { .. }
+        | LatestJsonError::NoSignedPlatforms
+        | LatestJsonError::UnsignedInstallers { .. }
+        | LatestJsonError::SignatureVerificationFailed { .. } => 3,
+        _ => 1,
+    }
 }
 
 fn main() {
-    let args = Args::parse();
+    let cli = Cli::parse();
+    init_logger(cli.verbose);
 
-    // Keep positional help/version for backward compatibility with existing scripts.
-    if let Some(ref first_arg) = args.download_url_base {
-        match first_arg.as_str() {
-            "help" => {
-                Args::command().print_help().unwrap();
-                return;
+    let result = match cli.command {
+        Some(Commands::Generate(args)) => args.run(),
+        Some(Commands::Verify(args)) => args.run(),
+        Some(Commands::Inspect { bundle_dir }) => match bundle_dir {
+            Some(dir) => tauri_latest_json::inspect_bundle(&dir),
+            None => tauri_latest_json::inspect_bundle_auto(),
+        }
+        .map(|installers| {
+            for installer in installers {
+                let signature = match installer.signature_path {
+                    Some(_) => "signed".green(),
+                    None => "unsigned".yellow(),
+                };
+                println!(
+                    "{:<18} {:<9} {}",
+                    installer.platform_key,
+                    signature,
+                    installer.path.display()
+                );
+            }
+        }),
+        #[cfg(feature = "sign")]
+        Some(Commands::Sign {
+            bundle_dir,
+            private_key,
+            password,
+        }) => {
+            let private_key = private_key.as_deref();
+            let password = password.as_deref();
+            match bundle_dir {
+                Some(dir) => tauri_latest_json::sign_installers(&dir, private_key, password),
+                None => tauri_latest_json::sign_installers_auto(private_key, password),
             }
-            "version" => {
+            .map(|written| {
+                println!(
+                    "{} signed {} installer(s)",
+                    "✔".green().bold(),
+                    written.len()
+                );
+            })
+        }
+        None => {
+            // Keep positional version for backward compatibility with existing scripts;
+            // positional `help` is handled by clap's help subcommand.
+            if cli.download_url_base.as_deref() == Some("version") {
                 println!("tauri-latest-json {}", tauri_latest_json::VERSION);
                 return;
             }
-            _ => {}
+            tauri_latest_json::run_with_optional_args(cli.download_url_base, cli.notes)
         }
-    }
+    };
 
-    if let Err(e) = tauri_latest_json::run_with_optional_args(args.download_url_base, args.notes) {
+    if let Err(e) = result {
         eprintln!("{} {}", "error:".red().bold(), e);
-        std::process::exit(1);
+        std::process::exit(exit_code(&e));
     }
 }
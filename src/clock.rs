@@ -0,0 +1,169 @@
+//! Time source for the manifest `pub_date`.
+//!
+//! `pub_date` is computed with `chrono` by default. Building with
+//! `default-features = false, features = ["time"]` uses the `time` crate
+//! instead; when both features are enabled, `chrono` is used. Either way the
+//! written string is identical.
+
+#[cfg(not(any(feature = "chrono", feature = "time")))]
+compile_error!("enable either the `chrono` or the `time` feature for `pub_date`");
+
+use crate::Result;
+
+/// The instant type clocks return: `chrono::DateTime<Utc>` with the `chrono`
+/// feature, or `time::OffsetDateTime` with only `time`.
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// The instant type clocks return: `chrono::DateTime<Utc>` with the `chrono`
+/// feature, or `time::OffsetDateTime` with only `time`.
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+pub type Timestamp = time::OffsetDateTime;
+
+/// Supplies the timestamp written to `pub_date`.
+///
+/// Implement this to make output deterministic in tests or to backdate a
+/// re-published manifest. A fixed [`Timestamp`] is itself a clock.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Timestamp;
+}
+
+/// The default clock, backed by the system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[cfg(feature = "chrono")]
+    fn now(&self) -> Timestamp {
+        chrono::Utc::now()
+    }
+
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    fn now(&self) -> Timestamp {
+        time::OffsetDateTime::now_utc()
+    }
+}
+
+impl Clock for Timestamp {
+    fn now(&self) -> Timestamp {
+        *self
+    }
+}
+
+/// How `pub_date` is written. Every format is applied to the UTC time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PubDateFormat {
+    /// RFC 3339 with whole seconds, e.g. `2024-05-01T12:30:00Z`.
+    #[default]
+    Rfc3339Secs,
+    /// RFC 3339 with milliseconds, e.g. `2024-05-01T12:30:00.123Z`.
+    Rfc3339Millis,
+    /// A layout in the date backend's syntax: a strftime string such as
+    /// `%Y-%m-%d %H:%M:%S` with `chrono`, or a format description such as
+    /// `[year]-[month]-[day] [hour]:[minute]:[second]` with only `time`.
+    Custom(String),
+}
+
+/// Formats `instant` as `format` says.
+#[cfg(feature = "chrono")]
+pub(crate) fn format_pub_date(instant: &Timestamp, format: &PubDateFormat) -> Result<String> {
+    use chrono::{format::StrftimeItems, SecondsFormat};
+
+    match format {
+        PubDateFormat::Rfc3339Secs => Ok(instant.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        PubDateFormat::Rfc3339Millis => Ok(instant.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        PubDateFormat::Custom(layout) => {
+            let items = StrftimeItems::new(layout)
+                .parse()
+                .map_err(|_| invalid_layout(layout))?;
+            Ok(instant.format_with_items(items.iter()).to_string())
+        }
+    }
+}
+
+/// Formats `instant` as `format` says.
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+pub(crate) fn format_pub_date(instant: &Timestamp, format: &PubDateFormat) -> Result<String> {
+    use time::format_description::{self, well_known::Rfc3339};
+
+    let utc = instant.to_offset(time::UtcOffset::UTC);
+    let formatted = match format {
+        PubDateFormat::Rfc3339Secs => utc.replace_nanosecond(0).unwrap_or(utc).format(&Rfc3339),
+        PubDateFormat::Rfc3339Millis => utc.format(
+            &format_description::parse_borrowed::<2>(
+                "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z",
+            )
+            .expect("the millisecond layout is valid"),
+        ),
+        PubDateFormat::Custom(layout) => {
+            let items = format_description::parse_borrowed::<2>(layout)
+                .map_err(|_| invalid_layout(layout))?;
+            utc.format(&items)
+        }
+    };
+    formatted.map_err(|e| crate::LatestJsonError::InvalidPubDate(e.to_string()))
+}
+
+/// Whether `value` parses as an RFC 3339 timestamp.
+#[cfg(feature = "chrono")]
+pub(crate) fn is_rfc3339(value: &str) -> bool {
+    chrono::DateTime::parse_from_rfc3339(value).is_ok()
+}
+
+/// Whether `value` parses as an RFC 3339 timestamp.
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+pub(crate) fn is_rfc3339(value: &str) -> bool {
+    time::OffsetDateTime::parse(value, &time::format_description::well_known::Rfc3339).is_ok()
+}
+
+fn invalid_layout(layout: &str) -> crate::LatestJsonError {
+    crate::LatestJsonError::InvalidPubDate(format!("invalid custom format '{}'", layout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSTANT_NANOS: i64 = 1_714_566_600_123_456_789;
+
+    #[cfg(feature = "chrono")]
+    fn instant() -> Timestamp {
+        chrono::DateTime::from_timestamp_nanos(INSTANT_NANOS)
+    }
+
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    fn instant() -> Timestamp {
+        time::OffsetDateTime::from_unix_timestamp_nanos(INSTANT_NANOS.into()).unwrap()
+    }
+
+    #[cfg(feature = "chrono")]
+    const DATE_LAYOUT: &str = "%Y%m%d";
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    const DATE_LAYOUT: &str = "[year][month][day]";
+
+    fn format(format: PubDateFormat) -> Result<String> {
+        format_pub_date(&instant(), &format)
+    }
+
+    #[test]
+    fn test_format_pub_date_matches_across_backends() {
+        assert_eq!(
+            format(PubDateFormat::Rfc3339Secs).unwrap(),
+            "2024-05-01T12:30:00Z"
+        );
+        assert_eq!(
+            format(PubDateFormat::Rfc3339Millis).unwrap(),
+            "2024-05-01T12:30:00.123Z"
+        );
+        assert_eq!(
+            format(PubDateFormat::Custom(DATE_LAYOUT.to_string())).unwrap(),
+            "20240501"
+        );
+        assert!(matches!(
+            format(PubDateFormat::Custom("[year %".to_string())),
+            Err(crate::LatestJsonError::InvalidPubDate(_))
+        ));
+        assert_eq!(instant().now(), instant());
+    }
+}
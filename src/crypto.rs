@@ -0,0 +1,256 @@
+//! Pluggable signing and verification, so discovery and manifest assembly
+//! do not depend on one signature scheme.
+//!
+//! Minisign (what Tauri uses) is the default: [`MinisignVerifier`] with the
+//! `verify-signature` feature and [`MinisignSigner`] with `sign`. Callers can
+//! supply their own [`Verifier`] or [`Signer`], e.g. a mock in tests.
+//!
+//! [`MinisignVerifier`]: crate::MinisignVerifier
+//! [`MinisignSigner`]: crate::MinisignSigner
+
+use crate::{
+    error::WithPath, file_name_lossy, find_installers, FileSystem, LatestJsonError, Result,
+};
+use std::{
+    fmt, fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Checks an updater signature.
+pub trait Verifier: Send + Sync {
+    /// Returns `Ok` when `signature` (the `.sig` content) is valid for
+    /// `data` under `public_key` (the configured key string).
+    fn verify(
+        &self,
+        data: &[u8],
+        signature: &str,
+        public_key: &str,
+    ) -> std::result::Result<(), VerifyError>;
+}
+
+/// Produces updater signatures.
+pub trait Signer {
+    /// Returns the `.sig` content for everything read from `data`, which
+    /// streams the installer so it is never fully loaded.
+    fn sign(&self, data: &mut dyn Read) -> std::result::Result<String, SignError>;
+
+    /// Signs the installer named `installer_name`. Defaults to
+    /// [`sign`](Self::sign); minisign records the name in the trusted
+    /// comment, as `tauri signer sign` does.
+    fn sign_file(
+        &self,
+        installer_name: &str,
+        data: &mut dyn Read,
+    ) -> std::result::Result<String, SignError> {
+        let _ = installer_name;
+        self.sign(data)
+    }
+}
+
+/// Why a [`Verifier`] rejected a signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyError {
+    pub reason: String,
+}
+
+impl VerifyError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Why a [`Signer`] could not sign.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignError {
+    pub reason: String,
+}
+
+impl SignError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for SignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl std::error::Error for SignError {}
+
+/// Signs every installer in `bundle_dir` that has no adjacent signature file
+/// with `signer`, writing `<installer>.<signature_extension>` (leading `.`
+/// optional, as for [`GenerateOptions::signature_extension`]). `.dmg` files
+/// are skipped because the updater never downloads them. Returns the paths
+/// of the signature files written.
+///
+/// [`GenerateOptions::signature_extension`]: crate::GenerateOptions::signature_extension
+pub fn sign_installers_with(
+    bundle_dir: &Path,
+    signer: &dyn Signer,
+    signature_extension: &str,
+) -> Result<Vec<PathBuf>> {
+    let signature_extension = signature_extension.trim_start_matches('.');
+    let mut written = Vec::new();
+    for installer in find_installers(bundle_dir)? {
+        let installer_name = file_name_lossy(&installer);
+        if installer_name.ends_with(".dmg") {
+            continue;
+        }
+        let mut sig_path = installer.clone().into_os_string();
+        sig_path.push(".");
+        sig_path.push(signature_extension);
+        let sig_path = PathBuf::from(sig_path);
+        if sig_path.exists() {
+            log::debug!("{} is already signed", installer_name);
+            continue;
+        }
+
+        let mut data = fs::File::open(&installer).with_path(&installer)?;
+        let signature = signer.sign_file(&installer_name, &mut data).map_err(|e| {
+            LatestJsonError::SigningFailed {
+                installer: installer.clone(),
+                reason: e.reason,
+            }
+        })?;
+        fs::write(&sig_path, signature).with_path(&sig_path)?;
+        log::info!("signed {}", installer_name);
+        written.push(sig_path);
+    }
+    Ok(written)
+}
+
+/// Keeps `GenerateOptions` cloneable and debuggable with any verifier.
+#[derive(Clone)]
+pub(crate) struct SharedVerifier(Arc<dyn Verifier>);
+
+impl SharedVerifier {
+    pub(crate) fn new(verifier: impl Verifier + 'static) -> Self {
+        Self(Arc::new(verifier))
+    }
+}
+
+impl fmt::Debug for SharedVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Verifier")
+    }
+}
+
+/// Verifies `installer` with `verifier`, or with minisign (streaming the
+/// file) when none is set.
+pub(crate) fn verify_installer(
+    verifier: Option<&SharedVerifier>,
+    filesystem: &dyn FileSystem,
+    installer: &Path,
+    signature: &str,
+    public_keys: &[String],
+) -> Result<()> {
+    match verifier {
+        Some(SharedVerifier(verifier)) => {
+            let data = filesystem.read(installer).with_path(installer)?;
+            verify_any_key(installer, public_keys, |public_key| {
+                verifier.verify(&data, signature, public_key).map_err(|e| {
+                    LatestJsonError::SignatureVerificationFailed {
+                        installer: installer.to_path_buf(),
+                        reason: e.reason,
+                    }
+                })
+            })
+        }
+        #[cfg(feature = "verify-signature")]
+        None => crate::signature::verify_signature(filesystem, installer, signature, public_keys),
+        #[cfg(not(feature = "verify-signature"))]
+        None => {
+            let _ = (filesystem, signature, public_keys);
+            Err(LatestJsonError::SignatureVerificationFailed {
+                installer: installer.to_path_buf(),
+                reason: "no verifier: enable the verify-signature feature or set GenerateOptions::verifier".to_string(),
+            })
+        }
+    }
+}
+
+/// Passes when `verify` accepts any of `public_keys`, e.g. the old and new
+/// key during a rotation; otherwise fails with every key's reason.
+pub(crate) fn verify_any_key(
+    installer: &Path,
+    public_keys: &[String],
+    mut verify: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let mut reasons = Vec::new();
+    for public_key in public_keys {
+        match verify(public_key) {
+            Ok(()) => return Ok(()),
+            Err(LatestJsonError::SignatureVerificationFailed { reason, .. }) => {
+                reasons.push(reason)
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    let reason = match reasons.len() {
+        0 => "no public key to verify against".to_string(),
+        1 => reasons.remove(0),
+        _ => format!("no public key verified it ({})", reasons.join("; ")),
+    };
+    Err(LatestJsonError::SignatureVerificationFailed {
+        installer: installer.to_path_buf(),
+        reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{make_temp_dir, write_file};
+
+    struct ReverseSigner;
+
+    impl Signer for ReverseSigner {
+        fn sign(&self, data: &mut dyn Read) -> std::result::Result<String, SignError> {
+            let mut bytes = Vec::new();
+            data.read_to_end(&mut bytes)
+                .map_err(|e| SignError::new(e.to_string()))?;
+            if bytes.is_empty() {
+                return Err(SignError::new("nothing to sign"));
+            }
+            Ok(bytes.iter().rev().map(|b| *b as char).collect())
+        }
+    }
+
+    #[test]
+    fn test_sign_installers_with_uses_custom_signer() {
+        let dir = make_temp_dir();
+        write_file(&dir.join("app_1.0.0_amd64.AppImage"), "linux");
+        write_file(&dir.join("app_1.0.0_x64.dmg"), "dmg");
+
+        let written = sign_installers_with(&dir, &ReverseSigner, "sig").unwrap();
+
+        assert_eq!(written, vec![dir.join("app_1.0.0_amd64.AppImage.sig")]);
+        assert_eq!(fs::read_to_string(&written[0]).unwrap(), "xunil");
+        let written = sign_installers_with(&dir, &ReverseSigner, ".minisig").unwrap();
+        assert_eq!(written, vec![dir.join("app_1.0.0_amd64.AppImage.minisig")]);
+
+        write_file(&dir.join("app_1.0.0_x64_en-US.msi"), "");
+        let err = sign_installers_with(&dir, &ReverseSigner, "sig").unwrap_err();
+        assert!(matches!(
+            err,
+            LatestJsonError::SigningFailed { reason, .. } if reason == "nothing to sign"
+        ));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,225 @@
+//! What changed between two manifests, e.g. the live one and the next.
+
+use crate::LatestJson;
+use std::fmt;
+
+/// The changes from one manifest to another, as returned by
+/// [`diff_manifests`]. `Display` prints one line per change, in the style of
+/// a unified diff (`+` added, `-` removed, `~` changed).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestDiff {
+    /// `(old, new)` when `version` changed.
+    pub version: Option<(String, String)>,
+    /// `(old, new)` when `pub_date` changed.
+    pub pub_date: Option<(String, String)>,
+    /// Whether the top-level `notes` changed.
+    pub notes_changed: bool,
+    /// `(old, new)` when `critical` changed.
+    pub critical: Option<(bool, bool)>,
+    /// `(old, new)` when `minimum_version` changed.
+    pub minimum_version: Option<(Option<String>, Option<String>)>,
+    /// Other top-level fields (`extra`) added, removed, or changed, by key.
+    pub extra_changed: Vec<String>,
+    /// Platform keys only in the new manifest.
+    pub added: Vec<String>,
+    /// Platform keys only in the old manifest.
+    pub removed: Vec<String>,
+    /// Platforms in both whose entry changed.
+    pub changed: Vec<PlatformChange>,
+}
+
+/// How one platform's entry changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlatformChange {
+    pub platform: String,
+    /// `(old, new)` when the download URL changed.
+    pub url: Option<(String, String)>,
+    /// Whether the signature changed.
+    pub signature_changed: bool,
+    /// Whether anything else in the entry changed (notes, checksum, extra
+    /// fields).
+    pub other_changed: bool,
+}
+
+impl ManifestDiff {
+    /// True when the manifests are the same.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Compares two manifests, e.g. the live `latest.json` against the one about
+/// to be published.
+///
+/// ```
+/// use tauri_latest_json::{diff_manifests, LatestJson};
+///
+/// let old: LatestJson = serde_json::from_str(
+///     r#"{"version":"1.0.0","pub_date":"2024-05-01T12:30:00Z","platforms":{}}"#,
+/// )?;
+/// let mut new = old.clone();
+/// new.version = "1.1.0".to_string();
+/// let diff = diff_manifests(&old, &new);
+/// assert_eq!(diff.version, Some(("1.0.0".to_string(), "1.1.0".to_string())));
+/// assert_eq!(diff.to_string(), "~ version: 1.0.0 -> 1.1.0");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub fn diff_manifests(old: &LatestJson, new: &LatestJson) -> ManifestDiff {
+    let changed_pair =
+        |old: &String, new: &String| (old != new).then(|| (old.clone(), new.clone()));
+    let mut diff = ManifestDiff {
+        version: changed_pair(&old.version, &new.version),
+        pub_date: changed_pair(&old.pub_date, &new.pub_date),
+        notes_changed: old.notes != new.notes,
+        critical: (old.critical != new.critical).then_some((old.critical, new.critical)),
+        minimum_version: (old.minimum_version != new.minimum_version)
+            .then(|| (old.minimum_version.clone(), new.minimum_version.clone())),
+        ..Default::default()
+    };
+    let mut extra_keys: Vec<&String> = old.extra.keys().chain(new.extra.keys()).collect();
+    extra_keys.sort();
+    extra_keys.dedup();
+    diff.extra_changed = extra_keys
+        .into_iter()
+        .filter(|key| old.extra.get(*key) != new.extra.get(*key))
+        .cloned()
+        .collect();
+    for (platform, new_entry) in &new.platforms {
+        let Some(old_entry) = old.platforms.get(platform) else {
+            diff.added.push(platform.clone());
+            continue;
+        };
+        if old_entry == new_entry {
+            continue;
+        }
+        let (mut old_rest, mut new_rest) = (old_entry.clone(), new_entry.clone());
+        for rest in [&mut old_rest, &mut new_rest] {
+            rest.url.clear();
+            rest.signature.clear();
+        }
+        diff.changed.push(PlatformChange {
+            platform: platform.clone(),
+            url: changed_pair(&old_entry.url, &new_entry.url),
+            signature_changed: old_entry.signature != new_entry.signature,
+            other_changed: old_rest != new_rest,
+        });
+    }
+    diff.removed = old
+        .platforms
+        .keys()
+        .filter(|platform| !new.platforms.contains_key(*platform))
+        .cloned()
+        .collect();
+    diff
+}
+
+impl fmt::Display for ManifestDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        if let Some((old, new)) = &self.version {
+            lines.push(format!("~ version: {} -> {}", old, new));
+        }
+        if let Some((old, new)) = &self.pub_date {
+            lines.push(format!("~ pub_date: {} -> {}", old, new));
+        }
+        if self.notes_changed {
+            lines.push("~ notes".to_string());
+        }
+        if let Some((old, new)) = &self.critical {
+            lines.push(format!("~ critical: {} -> {}", old, new));
+        }
+        if let Some((old, new)) = &self.minimum_version {
+            let show = |version: &Option<String>| version.clone().unwrap_or_else(|| "none".into());
+            lines.push(format!("~ minimum_version: {} -> {}", show(old), show(new)));
+        }
+        lines.extend(self.extra_changed.iter().map(|key| format!("~ {}", key)));
+        lines.extend(self.added.iter().map(|platform| format!("+ {}", platform)));
+        lines.extend(
+            self.removed
+                .iter()
+                .map(|platform| format!("- {}", platform)),
+        );
+        for change in &self.changed {
+            if let Some((old, new)) = &change.url {
+                lines.push(format!("~ {} url: {} -> {}", change.platform, old, new));
+            }
+            if change.signature_changed {
+                lines.push(format!("~ {} signature", change.platform));
+            }
+            if change.other_changed {
+                lines.push(format!("~ {} other fields", change.platform));
+            }
+        }
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn manifest(version: &str, platforms: serde_json::Value) -> LatestJson {
+        serde_json::from_value(json!({
+            "version": version,
+            "pub_date": "2024-05-01T12:30:00Z",
+            "platforms": platforms,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff_manifests_reports_version_and_platform_changes() {
+        let old = manifest(
+            "1.0.0",
+            json!({
+                "darwin-aarch64": { "signature": "a", "url": "https://cdn/1.0.0/App.app.tar.gz" },
+                "linux-armv7": { "signature": "b", "url": "https://cdn/1.0.0/app.AppImage" },
+                "windows-x86_64": { "signature": "c", "url": "https://cdn/app.msi" },
+            }),
+        );
+        let new = manifest(
+            "1.1.0",
+            json!({
+                "darwin-aarch64": { "signature": "d", "url": "https://cdn/1.1.0/App.app.tar.gz" },
+                "windows-aarch64": { "signature": "e", "url": "https://cdn/app_arm64.msi" },
+                "windows-x86_64": { "signature": "c", "url": "https://cdn/app.msi", "sha256": "ff" },
+            }),
+        );
+
+        let diff = diff_manifests(&old, &new);
+        assert_eq!(diff.added, ["windows-aarch64"]);
+        assert_eq!(diff.removed, ["linux-armv7"]);
+        assert_eq!(
+            diff.to_string(),
+            "~ version: 1.0.0 -> 1.1.0\n\
+             + windows-aarch64\n\
+             - linux-armv7\n\
+             ~ darwin-aarch64 url: https://cdn/1.0.0/App.app.tar.gz -> https://cdn/1.1.0/App.app.tar.gz\n\
+             ~ darwin-aarch64 signature\n\
+             ~ windows-x86_64 other fields"
+        );
+        assert!(diff_manifests(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_manifests_reports_top_level_fields() {
+        let old = manifest("1.0.0", json!({}));
+        let mut new = old.clone();
+        new.critical = true;
+        new.minimum_version = Some("0.9.0".to_string());
+        new.extra
+            .insert("homepage".to_string(), json!("https://example.com"));
+
+        let diff = diff_manifests(&old, &new);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.critical, Some((false, true)));
+        assert_eq!(diff.extra_changed, ["homepage"]);
+        assert_eq!(
+            diff.to_string(),
+            "~ critical: false -> true\n\
+             ~ minimum_version: none -> 0.9.0\n\
+             ~ homepage"
+        );
+    }
+}
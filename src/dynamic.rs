@@ -0,0 +1,124 @@
+//! Per-target responses for Tauri's dynamic updater endpoint format.
+//!
+//! A dynamic endpoint such as `https://cdn.example.com/{{target}}/{{arch}}.json`
+//! answers with a single platform's update instead of the whole manifest.
+//! The updater still compares `version` against the running app, so a static
+//! host can serve one file per target and ignore `{{current_version}}`.
+
+use crate::{FileSystem, LatestJson, RealFs, Result};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Builds the dynamic response for every platform in `latest`, keyed by
+/// route `{target}/{arch}` (e.g. `darwin/aarch64` for `darwin-aarch64`).
+///
+/// Each body has `version`, `pub_date`, `url`, `signature`, and `notes`
+/// (the platform's own notes when set, otherwise the manifest's).
+pub fn dynamic_manifests(latest: &LatestJson) -> BTreeMap<String, Value> {
+    latest
+        .platforms
+        .iter()
+        .map(|(platform, entry)| {
+            let route = match platform.split_once('-') {
+                Some((target, arch)) => format!("{}/{}", target, arch),
+                None => platform.clone(),
+            };
+            let body = json!({
+                "version": latest.version,
+                "pub_date": latest.pub_date,
+                "url": entry.url,
+                "signature": entry.signature,
+                "notes": entry.notes.as_deref().unwrap_or(&latest.notes),
+            });
+            (route, body)
+        })
+        .collect()
+}
+
+/// Writes [`dynamic_manifests`] under `out_dir` as `{target}/{arch}.json`,
+/// ready for a static server behind an endpoint like
+/// `https://cdn.example.com/updates/{{target}}/{{arch}}.json`. Returns the
+/// written paths.
+pub fn generate_dynamic_manifests(latest: &LatestJson, out_dir: &Path) -> Result<Vec<PathBuf>> {
+    write_dynamic_manifests(&RealFs, latest, out_dir)
+}
+
+pub(crate) fn write_dynamic_manifests(
+    fs: &dyn FileSystem,
+    latest: &LatestJson,
+    out_dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for (route, body) in dynamic_manifests(latest) {
+        let path = out_dir.join(format!("{}.json", route));
+        fs.write(&path, serde_json::to_string_pretty(&body)?.as_bytes())?;
+        log::info!("dynamic manifest generated at {}", path.display());
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockFs;
+
+    fn sample() -> LatestJson {
+        serde_json::from_value(json!({
+            "version": "1.2.0",
+            "notes": "Bug fixes",
+            "pub_date": "2024-05-01T12:30:00Z",
+            "platforms": {
+                "darwin-aarch64": {
+                    "signature": "mac-sig",
+                    "url": "https://cdn/app.app.tar.gz",
+                    "notes": "Re-grant permissions"
+                },
+                "windows-x86_64": { "signature": "win-sig", "url": "https://cdn/app.msi" }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dynamic_manifests_key_routes_by_target_and_arch() {
+        let routes = dynamic_manifests(&sample());
+
+        assert_eq!(
+            routes.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["darwin/aarch64", "windows/x86_64"]
+        );
+        assert_eq!(
+            routes["windows/x86_64"],
+            json!({
+                "version": "1.2.0",
+                "pub_date": "2024-05-01T12:30:00Z",
+                "url": "https://cdn/app.msi",
+                "signature": "win-sig",
+                "notes": "Bug fixes"
+            })
+        );
+        assert_eq!(routes["darwin/aarch64"]["notes"], "Re-grant permissions");
+    }
+
+    #[test]
+    fn test_write_dynamic_manifests_writes_one_file_per_route() {
+        let mock = MockFs::new();
+        let written = write_dynamic_manifests(&mock, &sample(), Path::new("/updates")).unwrap();
+
+        assert_eq!(
+            written,
+            [
+                PathBuf::from("/updates/darwin/aarch64.json"),
+                PathBuf::from("/updates/windows/x86_64.json"),
+            ]
+        );
+        let body: Value =
+            serde_json::from_slice(&mock.get(Path::new("/updates/windows/x86_64.json")).unwrap())
+                .unwrap();
+        assert_eq!(body["signature"], "win-sig");
+    }
+}
@@ -0,0 +1,389 @@
+//! Error type returned by the library API.
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+/// Result alias used throughout the crate.
+pub type Result<T, E = LatestJsonError> = std::result::Result<T, E>;
+
+/// Failure modes of `latest.json` generation.
+///
+/// Variants are matchable so callers can decide how to react, for example
+/// treating [`LatestJsonError::NoInstallers`] as a skip in CI.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LatestJsonError {
+    /// No bundle directory for `profile` was found in any of the `searched`
+    /// locations.
+    BundleDirNotFound {
+        searched: Vec<PathBuf>,
+        profile: String,
+    },
+    /// The bundle directory contains no recognized installers.
+    NoInstallers { bundle_dir: PathBuf },
+    /// `installer` was found but has no `.sig` next to it.
+    MissingSignature {
+        platform: String,
+        installer: PathBuf,
+    },
+    /// The selected installer's file name is not valid UTF-8, so no
+    /// download URL can name it.
+    NonUtf8FileName { path: PathBuf },
+    /// `installer`'s `.sig` is empty.
+    EmptySignature {
+        platform: String,
+        installer: PathBuf,
+    },
+    /// No platform key matches `installer`'s name and
+    /// `GenerateOptions::strict_platforms` is set.
+    UnknownPlatform { installer: String },
+    /// Every installer was skipped, so there is nothing to write.
+    NoSignedPlatforms,
+    /// Platforms listed in `GenerateOptions::expect_platforms` are not in
+    /// the manifest.
+    MissingExpectedPlatform { missing: Vec<String> },
+    /// No version could be read from any supported source.
+    VersionNotFound,
+    /// The git tag fallback failed (git missing, not a repo, or no tags).
+    GitVersion(String),
+    /// The version is not valid semver (see `GenerateOptions::strict_semver`).
+    InvalidVersion { found: String },
+    /// `GenerateOptions::minimum_version` is not semver or is newer than the
+    /// published `version`.
+    InvalidMinimumVersion { found: String, version: String },
+    /// The bundle holds installers for several versions and the
+    /// `mismatched` ones differ from `version` (see
+    /// `GenerateOptions::version_filter`).
+    MixedVersions {
+        version: String,
+        mismatched: Vec<PathBuf>,
+    },
+    /// Strict signing is on and these discovered installers have no
+    /// signature (see `GenerateOptions::require_all_signed`).
+    UnsignedInstallers { paths: Vec<PathBuf> },
+    /// Overwriting is off and a manifest already exists at `path`.
+    OutputExists { path: PathBuf },
+    /// `tauri.conf.json` was not found in any of the `searched` locations.
+    ConfigNotFound { searched: Vec<PathBuf> },
+    /// The config has no updater section at all.
+    UpdaterNotConfigured,
+    /// The config has an updater section but no public key in it.
+    PublicKeyMissing,
+    /// The resolved public key is not a well-formed minisign public key.
+    InvalidPublicKey(String),
+    /// `pub_date` could not be formatted: an invalid custom layout, or a
+    /// year RFC 3339 cannot represent.
+    InvalidPubDate(String),
+    /// The public key file at `path` could not be read or is empty.
+    PublicKeyFile { path: PathBuf, reason: String },
+    /// The download URL base is not an absolute `http(s)` URL.
+    InvalidDownloadUrl { url: String, reason: String },
+    /// An installer's signature does not match the public key.
+    SignatureVerificationFailed { installer: PathBuf, reason: String },
+    /// No private key was passed and `TAURI_SIGNING_PRIVATE_KEY` is unset.
+    PrivateKeyMissing,
+    /// The private key password is wrong.
+    InvalidKeyPassword,
+    /// The private key could not be decoded.
+    InvalidPrivateKey(String),
+    /// Signing `installer` failed.
+    SigningFailed { installer: PathBuf, reason: String },
+    /// More than one of literal notes, a notes file, and a changelog was given.
+    ConflictingNotes,
+    /// The changelog at `path` has no section for `version`.
+    ChangelogSectionMissing { path: PathBuf, version: String },
+    /// An existing `latest.json` is missing required fields.
+    InvalidManifest(String),
+    /// The generated manifest failed [`validate_manifest`](crate::validate_manifest).
+    Validation(ValidationError),
+    /// A required argument was missing and no terminal was available to prompt.
+    MissingArgument(&'static str),
+    /// Reading or parsing the file at `path` failed; `source` is the
+    /// [`Io`](Self::Io), [`Json`](Self::Json), [`Json5`](Self::Json5), or
+    /// [`Toml`](Self::Toml) error.
+    File {
+        path: PathBuf,
+        source: Box<LatestJsonError>,
+    },
+    /// Underlying filesystem or terminal error.
+    Io(io::Error),
+    /// Malformed JSON in `package.json`, `tauri.conf.json`, or a manifest.
+    Json(serde_json::Error),
+    /// Malformed `tauri.conf.json5`.
+    Json5(json5::Error),
+    /// Malformed `Cargo.toml`.
+    Toml(toml::de::Error),
+}
+
+impl fmt::Display for LatestJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BundleDirNotFound { searched, profile } => {
+                let build = match profile.as_str() {
+                    "release" => "tauri build".to_string(),
+                    "debug" => "tauri build --debug".to_string(),
+                    other => format!("a tauri build with the '{}' profile", other),
+                };
+                write!(
+                    f,
+                    "Could not detect bundle dir. Run {} first to produce target/{}/bundle. Searched:",
+                    build, profile
+                )?;
+                for path in searched {
+                    write!(f, "\n  - {}", path.display())?;
+                }
+                Ok(())
+            }
+            Self::NoInstallers { bundle_dir } => {
+                write!(f, "No installers found in {}", bundle_dir.display())
+            }
+            Self::MissingSignature { platform, installer } => write!(
+                f,
+                "Signature not found for {} (platform {}). Was TAURI_SIGNING_PRIVATE_KEY set during the build?",
+                installer.display(),
+                platform
+            ),
+            Self::NonUtf8FileName { path } => write!(
+                f,
+                "Installer file name is not valid UTF-8: {} (rename it so the updater URL can name it)",
+                path.display()
+            ),
+            Self::EmptySignature { platform, installer } => write!(
+                f,
+                "Signature for {} (platform {}) is empty; re-sign the installer",
+                installer.display(),
+                platform
+            ),
+            Self::UnknownPlatform { installer } => write!(
+                f,
+                "No platform matches {}; rename it, pass a platform mapper, or narrow the installer extensions",
+                installer
+            ),
+            Self::NoSignedPlatforms => write!(
+                f,
+                "No platforms with valid signatures found. Cannot generate latest.json."
+            ),
+            Self::MissingExpectedPlatform { missing } => write!(
+                f,
+                "Expected platforms missing from latest.json: {}. Did a build job fail?",
+                missing.join(", ")
+            ),
+            Self::VersionNotFound => write!(
+                f,
+                "Could not find version in package.json, Cargo.toml, or tauri.conf.json"
+            ),
+            Self::GitVersion(reason) => {
+                write!(f, "Could not read version from git tags: {}", reason)
+            }
+            Self::InvalidVersion { found } => write!(
+                f,
+                "Version '{}' is not valid semver (e.g. 1.2.3); the Tauri updater compares versions with semver rules",
+                found
+            ),
+            Self::InvalidMinimumVersion { found, version } => write!(
+                f,
+                "Minimum version '{}' must be valid semver no greater than the published version {}",
+                found, version
+            ),
+            Self::MixedVersions {
+                version,
+                mismatched,
+            } => {
+                write!(
+                    f,
+                    "Bundle contains installers from another build; these do not match version {} (remove them or use version_filter to skip them):",
+                    version
+                )?;
+                for path in mismatched {
+                    write!(f, "\n  - {}", path.display())?;
+                }
+                Ok(())
+            }
+            Self::OutputExists { path } => write!(
+                f,
+                "{} already exists; remove it or allow overwriting",
+                path.display()
+            ),
+            Self::UnsignedInstallers { paths } => {
+                write!(f, "All installers must be signed; these have no signature:")?;
+                for path in paths {
+                    write!(f, "\n  - {}", path.display())?;
+                }
+                Ok(())
+            }
+            Self::ConfigNotFound { searched } => {
+                write!(
+                    f,
+                    "Could not find tauri.conf.json in the current directory, its parents, or their src-tauri/. Searched:"
+                )?;
+                for path in searched {
+                    write!(f, "\n  - {}", path.display())?;
+                }
+                Ok(())
+            }
+            Self::UpdaterNotConfigured => write!(
+                f,
+                "No updater configured in tauri.conf.json: add the updater plugin with a pubkey (plugins.updater.pubkey for Tauri 2, tauri.updater.pubkey for Tauri 1)"
+            ),
+            Self::PublicKeyMissing => write!(
+                f,
+                "The updater in tauri.conf.json has no public key (checked plugins.updater.pubkey for Tauri 2 and tauri.updater.pubkey for Tauri 1)"
+            ),
+            Self::InvalidPublicKey(reason) => write!(
+                f,
+                "Invalid updater public key: {}. Use the .pub contents printed by `tauri signer generate`",
+                reason
+            ),
+            Self::InvalidPubDate(reason) => write!(f, "Cannot format pub_date: {}", reason),
+            Self::PublicKeyFile { path, reason } => write!(
+                f,
+                "Could not read public key file {}: {}",
+                path.display(),
+                reason
+            ),
+            Self::InvalidDownloadUrl { url, reason } => write!(
+                f,
+                "Invalid download URL '{}': {}. The updater requires absolute http(s) URLs",
+                url, reason
+            ),
+            Self::SignatureVerificationFailed { installer, reason } => write!(
+                f,
+                "Signature verification failed for {}: {}",
+                installer.display(),
+                reason
+            ),
+            Self::PrivateKeyMissing => write!(
+                f,
+                "No private key provided and TAURI_SIGNING_PRIVATE_KEY is not set"
+            ),
+            Self::InvalidKeyPassword => write!(
+                f,
+                "Wrong password for the private key (check TAURI_SIGNING_PRIVATE_KEY_PASSWORD)"
+            ),
+            Self::InvalidPrivateKey(reason) => write!(f, "Invalid private key: {}", reason),
+            Self::SigningFailed { installer, reason } => {
+                write!(f, "Failed to sign {}: {}", installer.display(), reason)
+            }
+            Self::ConflictingNotes => write!(
+                f,
+                "Release notes given more than once; use only one of notes, a notes file, or a changelog"
+            ),
+            Self::ChangelogSectionMissing { path, version } => write!(
+                f,
+                "No section for version {} in {}",
+                version,
+                path.display()
+            ),
+            Self::InvalidManifest(reason) => write!(f, "Invalid latest.json: {}", reason),
+            Self::Validation(e) => write!(f, "Generated latest.json is invalid: {}", e),
+            Self::MissingArgument(name) => {
+                write!(f, "Argument '{}' missing and not in a terminal.", name)
+            }
+            Self::File { path, source } => write!(f, "{}: {}", path.display(), source),
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::Json(e) => write!(f, "failed to parse JSON: {}", e),
+            Self::Json5(e) => write!(f, "failed to parse JSON5: {}", e),
+            Self::Toml(e) => write!(f, "failed to parse TOML: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LatestJsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::File { source, .. } => Some(source.as_ref()),
+            Self::Io(e) => Some(e),
+            Self::Json(e) => Some(e),
+            Self::Json5(e) => Some(e),
+            Self::Toml(e) => Some(e),
+            Self::Validation(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Why a manifest does not meet what the Tauri updater expects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// `version` is not semver.
+    InvalidVersion(String),
+    /// `pub_date` is not an RFC 3339 timestamp.
+    InvalidPubDate(String),
+    /// The `platforms` map is empty.
+    NoPlatforms,
+    /// The entry for `platform` has an empty `signature`.
+    EmptySignature { platform: String },
+    /// The entry for `platform` has an empty `url`.
+    EmptyUrl { platform: String },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidVersion(version) => write!(f, "version '{}' is not semver", version),
+            Self::InvalidPubDate(pub_date) => {
+                write!(f, "pub_date '{}' is not RFC 3339", pub_date)
+            }
+            Self::NoPlatforms => f.write_str("platforms is empty"),
+            Self::EmptySignature { platform } => {
+                write!(f, "platform {} has an empty signature", platform)
+            }
+            Self::EmptyUrl { platform } => write!(f, "platform {} has an empty url", platform),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl From<ValidationError> for LatestJsonError {
+    fn from(e: ValidationError) -> Self {
+        Self::Validation(e)
+    }
+}
+
+impl From<io::Error> for LatestJsonError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for LatestJsonError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+impl From<json5::Error> for LatestJsonError {
+    fn from(e: json5::Error) -> Self {
+        Self::Json5(e)
+    }
+}
+
+impl From<toml::de::Error> for LatestJsonError {
+    fn from(e: toml::de::Error) -> Self {
+        Self::Toml(e)
+    }
+}
+
+impl From<walkdir::Error> for LatestJsonError {
+    fn from(e: walkdir::Error) -> Self {
+        Self::Io(e.into())
+    }
+}
+
+/// Names the file behind a read or parse error (see [`LatestJsonError::File`]).
+pub(crate) trait WithPath<T> {
+    fn with_path(self, path: &Path) -> Result<T>;
+}
+
+impl<T, E: Into<LatestJsonError>> WithPath<T> for std::result::Result<T, E> {
+    fn with_path(self, path: &Path) -> Result<T> {
+        self.map_err(|e| LatestJsonError::File {
+            path: path.to_path_buf(),
+            source: Box::new(e.into()),
+        })
+    }
+}
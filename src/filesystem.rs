@@ -0,0 +1,291 @@
+//! Filesystem access used by manifest generation.
+//!
+//! The generation core reads installers and signatures and writes the
+//! manifest through [`FileSystem`], so it can run against [`MockFs`] in
+//! tests. Version and config discovery still read the real filesystem.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    ffi::OsStr,
+    fs,
+    io::{self, Cursor, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// How [`FileSystem::walk`] descends into a directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WalkOptions {
+    /// Deepest level listed; files directly in the walked directory are at
+    /// depth 1. Defaults to 4, enough for `bundle/<format>/<installer>`.
+    pub max_depth: usize,
+    /// Descends into symlinked directories and lists symlinked files.
+    /// Off by default, in which case symlinks are skipped.
+    pub follow_symlinks: bool,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            follow_symlinks: false,
+        }
+    }
+}
+
+/// The file operations generation needs.
+pub trait FileSystem: Send + Sync {
+    /// Reads the whole file at `path`.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// Replaces the file at `path` with `contents`, creating parent
+    /// directories as needed.
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+
+    /// Lists the files under `dir` down to `options.max_depth`, skipping
+    /// hidden directories (names starting with `.`) below `dir`. Each file
+    /// is listed once, even when symlinks make it reachable twice.
+    fn walk(&self, dir: &Path, options: WalkOptions) -> io::Result<Vec<PathBuf>>;
+
+    /// Returns true when a file exists at `path`.
+    fn exists(&self, path: &Path) -> bool;
+
+    /// Opens `path` for streaming reads. The default reads the whole file.
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send + '_>> {
+        Ok(Box::new(Cursor::new(self.read(path)?)))
+    }
+
+    /// Returns the size of the file at `path` in bytes. The default reads
+    /// the whole file.
+    fn file_size(&self, path: &Path) -> io::Result<u64> {
+        Ok(self.read(path)?.len() as u64)
+    }
+}
+
+/// The real filesystem. Writes go to a temp file next to the target and are
+/// renamed over it, so readers see either the old file or the complete new
+/// one; the shared directory keeps the rename on one filesystem.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealFs;
+
+impl FileSystem for RealFs {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        let temp_path = temp_path_for(path);
+        let written = (|| {
+            let mut file = fs::File::create(&temp_path)?;
+            file.write_all(contents)?;
+            file.sync_all()?;
+            fs::rename(&temp_path, path)
+        })();
+        if written.is_err() {
+            fs::remove_file(&temp_path).ok();
+        }
+        written
+    }
+
+    fn walk(&self, dir: &Path, options: WalkOptions) -> io::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        let mut seen = HashSet::new();
+        // Sorted, so the first path kept for a file is the same on every run
+        // and an installer and its `.sig` come from the same directory.
+        let entries = walkdir::WalkDir::new(dir)
+            .max_depth(options.max_depth)
+            .follow_links(options.follow_symlinks)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0 || !(entry.file_type().is_dir() && is_hidden(entry.file_name()))
+            });
+        for entry in entries {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let canonical = fs::canonicalize(entry.path()).unwrap_or_else(|_| entry.path().into());
+            if seen.insert(canonical) {
+                files.push(entry.into_path());
+            }
+        }
+        Ok(files)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send + '_>> {
+        Ok(Box::new(fs::File::open(path)?))
+    }
+
+    fn file_size(&self, path: &Path) -> io::Result<u64> {
+        Ok(fs::metadata(path)?.len())
+    }
+}
+
+/// Delay before the first retry of a failed read; each further retry waits
+/// twice as long.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// Wraps a filesystem so reads, opens, and size lookups that fail with a transient error
+/// (see [`is_transient`]) are retried up to `retries` times with
+/// exponential backoff. Writes and walks are passed through.
+pub(crate) struct RetryingFs<'a> {
+    inner: &'a dyn FileSystem,
+    retries: u32,
+}
+
+impl<'a> RetryingFs<'a> {
+    pub(crate) fn new(inner: &'a dyn FileSystem, retries: u32) -> Self {
+        Self { inner, retries }
+    }
+
+    fn retry<T>(&self, path: &Path, op: impl Fn() -> io::Result<T>) -> io::Result<T> {
+        let mut attempt = 0;
+        loop {
+            match op() {
+                Err(e) if attempt < self.retries && is_transient(&e) => {
+                    let delay = RETRY_BASE_DELAY * 2u32.pow(attempt);
+                    log::debug!(
+                        "reading {} failed ({}), retrying in {:?}",
+                        path.display(),
+                        e,
+                        delay
+                    );
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl FileSystem for RetryingFs<'_> {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.retry(path, || self.inner.read(path))
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.inner.write(path, contents)
+    }
+
+    fn walk(&self, dir: &Path, options: WalkOptions) -> io::Result<Vec<PathBuf>> {
+        self.inner.walk(dir, options)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.inner.exists(path)
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send + '_>> {
+        self.retry(path, || self.inner.open(path))
+    }
+
+    fn file_size(&self, path: &Path) -> io::Result<u64> {
+        self.retry(path, || self.inner.file_size(path))
+    }
+}
+
+/// Errors a network mount may return right after a build and then recover
+/// from: timeouts, interruptions, and (on Unix) `EIO`. `NotFound` and the
+/// like are permanent and fail at once.
+fn is_transient(e: &io::Error) -> bool {
+    // EIO has no `ErrorKind` of its own.
+    const EIO: i32 = 5;
+    matches!(
+        e.kind(),
+        io::ErrorKind::TimedOut | io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock
+    ) || (cfg!(unix) && e.raw_os_error() == Some(EIO))
+}
+
+fn is_hidden(name: &OsStr) -> bool {
+    name.to_string_lossy().starts_with('.')
+}
+
+/// The temp file an atomic write of `path` goes through: hidden, in the
+/// same directory, and unique per process.
+pub(crate) fn temp_path_for(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "latest.json".to_string());
+    path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()))
+}
+
+/// An in-memory filesystem for tests. Clones share the same files, so a
+/// test can keep one handle and inspect what generation wrote.
+#[derive(Debug, Clone, Default)]
+pub struct MockFs {
+    files: Arc<Mutex<BTreeMap<PathBuf, Vec<u8>>>>,
+}
+
+impl MockFs {
+    /// Creates an empty filesystem.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds (or replaces) a file, returning `self` for chaining.
+    pub fn with_file(self, path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) -> Self {
+        self.files().insert(path.into(), contents.into());
+        self
+    }
+
+    /// Returns the contents of `path`, if it exists.
+    pub fn get(&self, path: &Path) -> Option<Vec<u8>> {
+        self.files().get(path).cloned()
+    }
+
+    fn files(&self) -> std::sync::MutexGuard<'_, BTreeMap<PathBuf, Vec<u8>>> {
+        self.files
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+    }
+}
+
+impl FileSystem for MockFs {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.get(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} not found", path.display()),
+            )
+        })
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.files().insert(path.to_path_buf(), contents.to_vec());
+        Ok(())
+    }
+
+    fn walk(&self, dir: &Path, options: WalkOptions) -> io::Result<Vec<PathBuf>> {
+        Ok(self
+            .files()
+            .keys()
+            .filter(|path| {
+                let Ok(relative) = path.strip_prefix(dir) else {
+                    return false;
+                };
+                let depth = relative.components().count();
+                let mut parents = relative.iter().take(depth.saturating_sub(1));
+                depth <= options.max_depth && !parents.any(is_hidden)
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files().contains_key(path)
+    }
+}
@@ -0,0 +1,59 @@
+//! Step outputs for GitHub Actions.
+
+use crate::{GenerationSummary, Result};
+use std::{fs::OpenOptions, io::Write, path::Path};
+
+/// Set by GitHub Actions to the file that collects a step's outputs.
+pub const GITHUB_OUTPUT_ENV: &str = "GITHUB_OUTPUT";
+
+/// Appends `version=<version>` and `platforms=<key,key>` to the file named
+/// by [`GITHUB_OUTPUT_ENV`], so later steps can read
+/// `steps.<id>.outputs.version`. Returns `false` (and writes nothing) when
+/// the variable is unset, e.g. outside Actions.
+pub fn write_github_output(summary: &GenerationSummary) -> Result<bool> {
+    match std::env::var_os(GITHUB_OUTPUT_ENV) {
+        Some(path) if !path.is_empty() => {
+            append_github_output(Path::new(&path), summary)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+pub(crate) fn append_github_output(path: &Path, summary: &GenerationSummary) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "version={}", summary.version)?;
+    writeln!(file, "platforms={}", summary.platform_keys.join(","))?;
+    log::debug!("wrote step outputs to {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{make_temp_dir, write_file};
+
+    #[test]
+    fn test_append_github_output_keeps_existing_outputs() {
+        let dir = make_temp_dir();
+        let path = dir.join("github_output");
+        write_file(&path, "earlier=1\n");
+        let summary = GenerationSummary {
+            version: "1.2.0".to_string(),
+            version_file: None,
+            platform_keys: vec!["darwin-aarch64".to_string(), "windows-x86_64".to_string()],
+            installer_count: 2,
+            installers: Vec::new(),
+            unsigned: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        append_github_output(&path, &summary).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "earlier=1\nversion=1.2.0\nplatforms=darwin-aarch64,windows-x86_64\n"
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
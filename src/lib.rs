@@ -3,17 +3,62 @@
 //! This crate powers the `tauri-latest-json` CLI and can also be used as a
 //! library for automation workflows.
 
-use anyhow::{anyhow, Context, Result};
-use chrono::Utc;
-use colored::*;
-use dialoguer::Input;
-use serde_json::{json, Value};
+#[cfg(feature = "tokio")]
+mod async_api;
+mod clock;
+mod crypto;
+mod diff;
+mod dynamic;
+mod error;
+mod filesystem;
+mod github;
+mod manifest;
+mod notes;
+mod options;
+mod progress;
+mod report;
+#[cfg(feature = "sign")]
+mod sign;
+#[cfg(feature = "verify-signature")]
+mod signature;
+mod split;
+mod target;
+
+#[cfg(feature = "tokio")]
+pub use async_api::generate_latest_json_async;
+pub use clock::{Clock, PubDateFormat, SystemClock, Timestamp};
+pub use crypto::{sign_installers_with, SignError, Signer, Verifier, VerifyError};
+pub use diff::{diff_manifests, ManifestDiff, PlatformChange};
+pub use dynamic::{dynamic_manifests, generate_dynamic_manifests};
+pub use error::{LatestJsonError, Result, ValidationError};
+pub use filesystem::{FileSystem, MockFs, RealFs, WalkOptions};
+pub use github::{write_github_output, GITHUB_OUTPUT_ENV};
+pub use manifest::{
+    validate_manifest, GenerationSummary, InstallerInfo, InstallerStatus, LatestJson,
+    PlatformEntry, SignatureStatus, Warning,
+};
+pub use options::{GenerateOptions, OutputMode, OutputTarget};
+pub use progress::ProgressEvent;
+pub use report::{run_report, write_report};
+#[cfg(feature = "sign")]
+pub use sign::{sign_installers, MinisignSigner, PRIVATE_KEY_ENV, PRIVATE_KEY_PASSWORD_ENV};
 #[cfg(feature = "verify-signature")]
-use std::process::Command;
+pub use signature::{
+    verify_latest_json, verify_latest_json_with_keys, MinisignVerifier, VerificationFailure,
+};
+pub use split::{split_manifest, DEFAULT_PER_PLATFORM_FILE_NAME};
+pub use target::{detect_platform_key, Arch, Os, Target};
+
+#[cfg(feature = "cli")]
+use dialoguer::Input;
+use error::WithPath;
+use filesystem::RetryingFs;
+use rayon::prelude::*;
+use serde_json::Value;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
-    io::Read,
+    io::{Read, Write},
     path::{Path, PathBuf},
 };
 
@@ -24,6 +69,7 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 ///
 /// When run in a non-TTY context (for example CI), missing arguments return
 /// a clear error instead of prompting.
+#[cfg(feature = "cli")]
 pub fn run_with_optional_args(
     download_url_base: Option<String>,
     notes_parts: Vec<String>,
@@ -34,603 +80,4342 @@ pub fn run_with_optional_args(
         Some(url) => url,
         None => {
             if !is_tty {
-                return Err(anyhow!(
-                    "Argument 'download_url_base' missing and not in a terminal."
-                ));
+                return Err(LatestJsonError::MissingArgument("download_url_base"));
             }
-            println!(
-                "{} Argument 'download_url_base' missing. Entering interactive mode...",
-                "info:".cyan()
-            );
+            log::info!("Argument 'download_url_base' missing. Entering interactive mode...");
             Input::<String>::new()
                 .with_prompt("Enter the download URL base")
                 .interact_text()
-                .map_err(|_| anyhow!("Failed to read input"))?
+                .map_err(prompt_error)?
         }
     };
 
     let notes = if notes_parts.is_empty() {
         if !is_tty {
-            return Err(anyhow!("Argument 'notes' missing and not in a terminal."));
+            return Err(LatestJsonError::MissingArgument("notes"));
         }
         Input::<String>::new()
             .with_prompt("Enter the release notes")
             .interact_text()
-            .map_err(|_| anyhow!("Failed to read input"))?
+            .map_err(prompt_error)?
     } else {
         notes_parts.join(" ")
     };
 
-    generate_latest_json_auto(&download_url_base, &notes)
+    generate_latest_json_auto(&download_url_base, &notes).map(drop)
+}
+
+#[cfg(feature = "cli")]
+fn prompt_error(e: dialoguer::Error) -> LatestJsonError {
+    match e {
+        dialoguer::Error::IO(e) => LatestJsonError::Io(e),
+    }
+}
+
+/// Auto-detects the bundle dir, then signs installers lacking a `.sig`.
+///
+/// See [`sign_installers`] for key resolution.
+#[cfg(feature = "sign")]
+pub fn sign_installers_auto(
+    private_key: Option<&str>,
+    password: Option<&str>,
+) -> Result<Vec<PathBuf>> {
+    sign_installers(&detect_bundle_dir(None)?, private_key, password)
+}
+
+/// Runs [`verify_latest_json`] with the bundle dir and public key resolved
+/// the same way generation resolves them, unless given explicitly.
+#[cfg(feature = "verify-signature")]
+pub fn verify_latest_json_auto(
+    path: &Path,
+    bundle_dir: Option<&Path>,
+    tauri_conf_path: Option<&Path>,
+) -> Result<Vec<VerificationFailure>> {
+    let bundle_dir = match bundle_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => detect_bundle_dir(None)?,
+    };
+    let public_keys = resolve_public_key(None, tauri_conf_path)?;
+    for public_key in &public_keys {
+        validate_public_key(public_key)?;
+    }
+    verify_latest_json_with_keys(path, &bundle_dir, &public_keys)
 }
 
 /// Auto-detects bundle and config paths, then generates `latest.json`.
-pub fn generate_latest_json_auto(download_url_base: &str, notes: &str) -> Result<()> {
-    let bundle_dir = detect_bundle_dir()?;
-    let tauri_conf_path = detect_tauri_conf_path()?;
-    let public_key = read_public_key(&tauri_conf_path)?;
-    generate_latest_json(&bundle_dir, &public_key, download_url_base, notes)
+///
+/// Shorthand for `GenerateOptions::new(url).notes(notes).generate_with_summary()`.
+pub fn generate_latest_json_auto(
+    download_url_base: &str,
+    notes: &str,
+) -> Result<GenerationSummary> {
+    GenerateOptions::new(download_url_base)
+        .notes(notes)
+        .generate_with_summary()
+        .map(|(_, summary)| summary)
+}
+
+/// Like [`generate_latest_json_auto`], but reads the updater config from
+/// `tauri_conf_path` when given, writes the manifest to `output_path`, and
+/// applies `options`.
+pub fn generate_latest_json_auto_with(
+    download_url_base: &str,
+    notes: &str,
+    tauri_conf_path: Option<&Path>,
+    output_path: &Path,
+    options: &GenerateOptions,
+) -> Result<GenerationSummary> {
+    let mut options = options
+        .clone()
+        .with_url_and_notes(download_url_base, notes)
+        .output(output_path);
+    options.tauri_conf_path = tauri_conf_path.map(Path::to_path_buf);
+    options.generate_with_summary().map(|(_, summary)| summary)
 }
 
 /// Generates `latest.json` for a known bundle directory and updater public key.
+///
+/// The manifest is written to `output_path`; missing parent directories are
+/// created. Returns a [`GenerationSummary`] of the run.
 pub fn generate_latest_json(
     bundle_dir: &Path,
     public_key: &str,
     download_url_base: &str,
     notes: &str,
-) -> Result<()> {
-    let project_dir = std::env::current_dir().context("failed to get current directory")?;
-    generate_latest_json_for_project(
+    output_path: &Path,
+) -> Result<GenerationSummary> {
+    GenerateOptions::new(download_url_base)
+        .notes(notes)
+        .bundle_dir(bundle_dir)
+        .public_key(public_key)
+        .output(output_path)
+        .generate_with_summary()
+        .map(|(_, summary)| summary)
+}
+
+/// Like [`generate_latest_json`], with the optional knobs in `options`.
+pub fn generate_latest_json_with_options(
+    bundle_dir: &Path,
+    public_key: &str,
+    download_url_base: &str,
+    notes: &str,
+    output_path: &Path,
+    options: &GenerateOptions,
+) -> Result<GenerationSummary> {
+    options
+        .clone()
+        .with_url_and_notes(download_url_base, notes)
+        .bundle_dir(bundle_dir)
+        .public_key(public_key)
+        .output(output_path)
+        .generate_with_summary()
+        .map(|(_, summary)| summary)
+}
+
+/// Like [`generate_latest_json`], but consults `mapper` for each installer
+/// filename before the built-in platform detection. Returning `None` falls
+/// back to the default mapping.
+///
+/// ```no_run
+/// # use std::path::Path;
+/// tauri_latest_json::generate_latest_json_with_mapper(
+///     Path::new("src-tauri/target/release/bundle"),
+///     "pubkey",
+///     "https://example.com/downloads",
+///     "notes",
+///     Path::new("latest.json"),
+///     |name| name.contains("armhf").then(|| "linux-armv7".to_string()),
+/// )?;
+/// # Ok::<(), tauri_latest_json::LatestJsonError>(())
+/// ```
+pub fn generate_latest_json_with_mapper<F>(
+    bundle_dir: &Path,
+    public_key: &str,
+    download_url_base: &str,
+    notes: &str,
+    output_path: &Path,
+    mut mapper: F,
+) -> Result<GenerationSummary>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let project_dir = std::env::current_dir()?;
+    let options = GenerateOptions::new(download_url_base).notes(notes);
+    let (latest_json, summary) = build_latest_json_with_summary(
+        bundle_dir,
+        &[public_key.to_string()],
+        &project_dir,
+        &options,
+        &mut mapper,
+    )?;
+    write_latest_json(&RealFs, &latest_json, output_path, true)?;
+    Ok(summary)
+}
+
+/// Builds the `latest.json` manifest without writing it anywhere.
+///
+/// The version is read from the current working directory, as with
+/// [`generate_latest_json`].
+pub fn build_latest_json(
+    bundle_dir: &Path,
+    public_key: &str,
+    download_url_base: &str,
+    notes: &str,
+) -> Result<LatestJson> {
+    let project_dir = std::env::current_dir()?;
+    build_latest_json_for_project(
         bundle_dir,
         public_key,
-        download_url_base,
-        notes,
         &project_dir,
+        &GenerateOptions::new(download_url_base).notes(notes),
+        &mut |_| None,
     )
 }
 
-fn read_version_from_dir(base: &Path) -> Result<String> {
-    let pkg_path = base.join("package.json");
-    if pkg_path.exists() {
-        let pkg_str = fs::read_to_string(&pkg_path).context("failed to read package.json")?;
-        let pkg_json: serde_json::Value =
-            serde_json::from_str(&pkg_str).context("failed to parse package.json")?;
-        if let Some(ver) = pkg_json["version"].as_str() {
-            return Ok(ver.to_string());
+/// Checks that an existing `latest.json` has the fields the Tauri updater
+/// requires: `version`, `pub_date`, and at least one platform with a
+/// `signature` and `url`.
+pub fn check_latest_json(path: &Path) -> Result<()> {
+    let manifest: Value =
+        serde_json::from_str(&fs::read_to_string(path).with_path(path)?).with_path(path)?;
+    let invalid = |reason: String| Err(LatestJsonError::InvalidManifest(reason));
+
+    for key in ["version", "pub_date"] {
+        if manifest[key].as_str().is_none_or(str::is_empty) {
+            return invalid(format!("missing or empty '{}'", key));
         }
     }
-
-    let tauri_conf_path = base.join("tauri.conf.json");
-    if tauri_conf_path.exists() {
-        let conf_str =
-            fs::read_to_string(&tauri_conf_path).context("failed to read tauri.conf.json")?;
-        let conf_json: serde_json::Value =
-            serde_json::from_str(&conf_str).context("failed to parse tauri.conf.json")?;
-        if let Some(ver) = conf_json["package"]["version"].as_str() {
-            return Ok(ver.to_string());
+    let platforms = match manifest["platforms"].as_object() {
+        Some(platforms) if !platforms.is_empty() => platforms,
+        _ => return invalid("'platforms' must be a non-empty object".to_string()),
+    };
+    for (platform, entry) in platforms {
+        for key in ["signature", "url"] {
+            if entry[key].as_str().is_none_or(str::is_empty) {
+                return invalid(format!("platform {} has no '{}'", platform, key));
+            }
         }
-        if let Some(ver) = conf_json["version"].as_str() {
-            return Ok(ver.to_string());
+    }
+    Ok(())
+}
+
+/// Which project file [`read_version`] consults first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VersionSource {
+    /// `package.json` first, then the rest in [`VersionSource::Auto`] order.
+    PackageJson,
+    /// `Cargo.toml` first, then the rest in [`VersionSource::Auto`] order.
+    CargoToml,
+    /// `tauri.conf.json` first, then the rest in [`VersionSource::Auto`] order.
+    TauriConf,
+    /// `package.json`, then `tauri.conf.json`, then `Cargo.toml`.
+    #[default]
+    Auto,
+}
+
+/// Which `package.json` [`GenerateOptions::package_json_lookup`] reads the
+/// version from, for monorepos whose apps have their own `package.json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PackageJsonLookup {
+    /// Only `package.json` in the project directory.
+    #[default]
+    Local,
+    /// The nearest `package.json` with a `version`, walking up from the
+    /// project directory.
+    Nearest,
+    /// The workspace root's `package.json`: the topmost ancestor whose
+    /// `package.json` has `workspaces` or that holds `pnpm-workspace.yaml`.
+    /// Falls back to [`PackageJsonLookup::Nearest`] outside a workspace.
+    WorkspaceRoot,
+}
+
+/// Reads the version from one kind of project file, if present, along with
+/// the file it came from.
+type VersionReader<'a> = &'a dyn Fn(&Path) -> Result<Option<(String, PathBuf)>>;
+
+/// Reads the app version from the project files in `base`.
+///
+/// Looks at `package.json`, `tauri.conf.json`/`tauri.conf.json5`, and
+/// `Cargo.toml`, each in `base` and `base/src-tauri`, starting with the
+/// source named by `prefer`. A config `"version"` naming a JSON file (Tauri
+/// 2 allows `"../package.json"`) is read from that file, relative to the
+/// config. The version is returned as written; no `v`
+/// stripping or semver check.
+pub fn read_version(base: &Path, prefer: VersionSource) -> Result<String> {
+    read_version_with_file(base, prefer, PackageJsonLookup::Local).map(|(version, _)| version)
+}
+
+/// Like [`read_version`], with `package.json` located by `lookup`, also
+/// returning the file the version was read from.
+pub(crate) fn read_version_with_file(
+    base: &Path,
+    prefer: VersionSource,
+    lookup: PackageJsonLookup,
+) -> Result<(String, PathBuf)> {
+    let package_json = |base: &Path| read_package_json_version(base, lookup);
+    let order: [VersionReader; 3] = match prefer {
+        VersionSource::PackageJson | VersionSource::Auto => [
+            &package_json,
+            &read_tauri_conf_version,
+            &read_cargo_toml_version,
+        ],
+        VersionSource::CargoToml => [
+            &read_cargo_toml_version,
+            &package_json,
+            &read_tauri_conf_version,
+        ],
+        VersionSource::TauriConf => [
+            &read_tauri_conf_version,
+            &package_json,
+            &read_cargo_toml_version,
+        ],
+    };
+    for read in order {
+        if let Some(found) = read(base)? {
+            return Ok(found);
         }
     }
+    Err(LatestJsonError::VersionNotFound)
+}
 
-    let src_tauri_conf_path = base.join("src-tauri").join("tauri.conf.json");
-    if src_tauri_conf_path.exists() {
-        let conf_str = fs::read_to_string(&src_tauri_conf_path)
-            .context("failed to read src-tauri/tauri.conf.json")?;
-        let conf_json: serde_json::Value =
-            serde_json::from_str(&conf_str).context("failed to parse src-tauri/tauri.conf.json")?;
-        if let Some(ver) = conf_json["package"]["version"].as_str() {
-            return Ok(ver.to_string());
+fn read_package_json_version(
+    base: &Path,
+    lookup: PackageJsonLookup,
+) -> Result<Option<(String, PathBuf)>> {
+    let nearest = || {
+        base.ancestors()
+            .map(|dir| dir.join("package.json"))
+            .collect()
+    };
+    let candidates: Vec<PathBuf> = match lookup {
+        PackageJsonLookup::Local => vec![base.join("package.json")],
+        PackageJsonLookup::Nearest => nearest(),
+        PackageJsonLookup::WorkspaceRoot => {
+            match base.ancestors().filter(|dir| is_workspace_root(dir)).last() {
+                Some(root) => vec![root.join("package.json")],
+                None => nearest(),
+            }
         }
-        if let Some(ver) = conf_json["version"].as_str() {
-            return Ok(ver.to_string());
+    };
+    for pkg_path in candidates {
+        if pkg_path.exists() {
+            let pkg_str = fs::read_to_string(&pkg_path).with_path(&pkg_path)?;
+            let pkg_json: serde_json::Value =
+                serde_json::from_str(&pkg_str).with_path(&pkg_path)?;
+            if let Some(ver) = pkg_json["version"].as_str() {
+                return Ok(Some((ver.to_string(), pkg_path)));
+            }
         }
     }
+    Ok(None)
+}
+
+/// A JS workspace root: pnpm's `pnpm-workspace.yaml`, or a `package.json`
+/// with `workspaces` (npm, yarn, bun).
+fn is_workspace_root(dir: &Path) -> bool {
+    dir.join("pnpm-workspace.yaml").is_file()
+        || fs::read_to_string(dir.join("package.json"))
+            .ok()
+            .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+            .is_some_and(|pkg| pkg.get("workspaces").is_some())
+}
 
-    let cargo_path = base.join("Cargo.toml");
-    if cargo_path.exists() {
-        let cargo_str = fs::read_to_string(&cargo_path).context("failed to read Cargo.toml")?;
-        let value: toml::Value =
-            toml::from_str(&cargo_str).context("failed to parse Cargo.toml")?;
-        if let Some(pkg) = value.get("package") {
-            if let Some(ver) = pkg.get("version").and_then(|v| v.as_str()) {
-                if !ver.is_empty() {
-                    return Ok(ver.to_string());
+fn read_tauri_conf_version(base: &Path) -> Result<Option<(String, PathBuf)>> {
+    for conf_dir in [base.to_path_buf(), base.join("src-tauri")] {
+        for name in TAURI_CONF_FILE_NAMES {
+            let conf_path = conf_dir.join(name);
+            if !conf_path.exists() {
+                continue;
+            }
+            let conf_json = read_tauri_conf(&conf_path)?;
+            if let Some(ver) = conf_json["package"]["version"].as_str() {
+                return Ok(Some((ver.to_string(), conf_path)));
+            }
+            if let Some(ver) = conf_json["version"].as_str() {
+                if ver.ends_with(".json") {
+                    let linked_path = conf_dir.join(ver);
+                    let linked_str = fs::read_to_string(&linked_path).with_path(&linked_path)?;
+                    let linked: Value =
+                        serde_json::from_str(&linked_str).with_path(&linked_path)?;
+                    return Ok(linked["version"]
+                        .as_str()
+                        .map(|ver| (ver.to_string(), linked_path)));
                 }
+                return Ok(Some((ver.to_string(), conf_path)));
             }
         }
     }
+    Ok(None)
+}
 
-    let src_tauri_cargo_path = base.join("src-tauri").join("Cargo.toml");
-    if src_tauri_cargo_path.exists() {
-        let cargo_str = fs::read_to_string(&src_tauri_cargo_path)
-            .context("failed to read src-tauri/Cargo.toml")?;
-        let value: toml::Value =
-            toml::from_str(&cargo_str).context("failed to parse src-tauri/Cargo.toml")?;
-        if let Some(pkg) = value.get("package") {
-            if let Some(ver) = pkg.get("version").and_then(|v| v.as_str()) {
-                if !ver.is_empty() {
-                    return Ok(ver.to_string());
-                }
+fn read_cargo_toml_version(base: &Path) -> Result<Option<(String, PathBuf)>> {
+    for cargo_path in [
+        base.join("Cargo.toml"),
+        base.join("src-tauri").join("Cargo.toml"),
+    ] {
+        if cargo_path.exists() {
+            if let Some(ver) = read_cargo_version(&cargo_path)? {
+                return Ok(Some((ver, cargo_path)));
             }
         }
     }
-
-    Err(anyhow!(
-        "Could not find version in package.json, Cargo.toml, or tauri.conf.json"
-    ))
+    Ok(None)
 }
 
-fn generate_latest_json_for_project(
-    bundle_dir: &Path,
-    public_key: &str,
-    download_url_base: &str,
-    notes: &str,
-    project_dir: &Path,
-) -> Result<()> {
-    let version = read_version_from_dir(project_dir)?;
-    println!("{} detected version: {}", "info:".cyan(), version.bold());
+/// Returns the most recent tag reachable from `HEAD` in `dir`.
+fn read_version_from_git(dir: &Path) -> Result<String> {
+    let output = std::process::Command::new("git")
+        .args(["describe", "--tags", "--abbrev=0"])
+        .current_dir(dir)
+        .output()
+        .map_err(|e| LatestJsonError::GitVersion(format!("could not run git: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(LatestJsonError::GitVersion(stderr.trim().to_string()));
+    }
+    let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(tag.strip_prefix('v').unwrap_or(&tag).to_string())
+}
 
-    let installers = find_installers_by_platform(bundle_dir)?;
-    if installers.is_empty() {
-        return Err(anyhow!("No installers found in {}", bundle_dir.display()));
+/// Strips a leading `v` and, when `strict` is set, requires valid semver,
+/// since the updater client compares versions with semver rules.
+fn normalize_version(version: &str, strict: bool) -> Result<String> {
+    let trimmed = version.trim();
+    let stripped = trimmed
+        .strip_prefix('v')
+        .or_else(|| trimmed.strip_prefix('V'))
+        .unwrap_or(trimmed);
+    if strict && semver::Version::parse(stripped).is_err() {
+        return Err(LatestJsonError::InvalidVersion {
+            found: version.to_string(),
+        });
     }
+    Ok(stripped.to_string())
+}
 
-    let signature_paths = find_signatures(bundle_dir)?;
-    let mut platforms = HashMap::new();
-    for (platform_key, installer) in installers {
-        let installer_name = match installer
-            .file_name()
-            .and_then(|s| s.to_str().map(|s| s.to_string()))
-        {
-            Some(s) => s,
-            None => continue,
-        };
+/// Normalizes `minimum` like the version and checks it does not exceed
+/// `version`.
+fn check_minimum_version(minimum: &str, version: &str) -> Result<String> {
+    let invalid = || LatestJsonError::InvalidMinimumVersion {
+        found: minimum.to_string(),
+        version: version.to_string(),
+    };
+    let normalized = normalize_version(minimum, true).map_err(|_| invalid())?;
+    let current = semver::Version::parse(version).map_err(|_| invalid())?;
+    match semver::Version::parse(&normalized) {
+        Ok(parsed) if parsed <= current => Ok(normalized),
+        _ => Err(invalid()),
+    }
+}
 
-        let sig_path = signature_paths.get(platform_key.as_str());
+/// Reads `package.version` from a `Cargo.toml`, resolving
+/// `version.workspace = true` against the workspace root's
+/// `[workspace.package]` version.
+fn read_cargo_version(cargo_path: &Path) -> Result<Option<String>> {
+    let value: toml::Value = toml::from_str(&fs::read_to_string(cargo_path).with_path(cargo_path)?)
+        .with_path(cargo_path)?;
+    let version = match value.get("package").and_then(|pkg| pkg.get("version")) {
+        Some(version) => version,
+        None => return Ok(None),
+    };
+    if let Some(ver) = version.as_str() {
+        return Ok((!ver.is_empty()).then(|| ver.to_string()));
+    }
 
-        if sig_path.is_none() {
-            if installer_name.ends_with(".dmg") {
-                eprintln!("{} No signature found for DMG on platform {}. Tauri doesn't generate .sig files for DMG, so it will be skipped.", "warning:".yellow().bold(), platform_key.bold());
-                continue;
-            } else {
-                return Err(anyhow!("Signature not found for platform {}", platform_key));
-            }
-        }
+    if version.get("workspace").and_then(|v| v.as_bool()) != Some(true) {
+        return Ok(None);
+    }
+    // The crate's own manifest may also be the workspace root.
+    let crate_dir = cargo_path.parent().unwrap_or(Path::new("."));
+    let root = match find_workspace_root(crate_dir) {
+        Some(root) => root,
+        None => return Ok(None),
+    };
+    let root_path = root.join("Cargo.toml");
+    let root_value: toml::Value =
+        toml::from_str(&fs::read_to_string(&root_path).with_path(&root_path)?)
+            .with_path(&root_path)?;
+    Ok(root_value
+        .get("workspace")
+        .and_then(|ws| ws.get("package"))
+        .and_then(|pkg| pkg.get("version"))
+        .and_then(|v| v.as_str())
+        .filter(|ver| !ver.is_empty())
+        .map(|ver| ver.to_string()))
+}
 
-        let mut f_sig = std::fs::File::open(sig_path.unwrap()).context(format!(
-            "failed to open signature file for {}",
-            platform_key
-        ))?;
-        let mut signature = String::new();
-        f_sig
-            .read_to_string(&mut signature)
-            .context(format!("failed to read signature for {}", platform_key))?;
+/// Hashes `path` in chunks so large installers are never fully loaded.
+fn sha256_file(fs: &dyn FileSystem, path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
 
-        #[cfg(feature = "verify-signature")]
-        {
-            verify_signature(&installer, &signature, public_key)?;
-        }
-        #[cfg(not(feature = "verify-signature"))]
-        {
-            let _ = &public_key;
+    let mut file = fs.open(path).with_path(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).with_path(path)?;
+        if n == 0 {
+            break;
         }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
 
-        println!(
-            "{} matched platform {}: {}",
-            "success:".green(),
-            platform_key.bold(),
-            installer_name.dimmed()
-        );
-
-        platforms.insert(
-            platform_key,
-            json!({
-                "signature": signature.trim(),
-                "url": format!("{}/{}", download_url_base, installer_name)
-            }),
-        );
+/// Requires an absolute `http(s)` URL with a host, which is all the updater
+/// accepts; `file://` is allowed only when `allow_file` is set.
+fn validate_download_url(download_url_base: &str, allow_file: bool) -> Result<()> {
+    let invalid = |reason: &str| LatestJsonError::InvalidDownloadUrl {
+        url: download_url_base.to_string(),
+        reason: reason.to_string(),
+    };
+    // Placeholders are substituted later; any plain token parses the same.
+    let candidate = ["{{version}}", "{{target}}", "{{arch}}", "{{file}}"]
+        .iter()
+        .fold(download_url_base.to_string(), |url, p| url.replace(p, "x"));
+    let url = url::Url::parse(&candidate).map_err(|e| invalid(&e.to_string()))?;
+    match url.scheme() {
+        "http" | "https" if url.host().is_some() => Ok(()),
+        "http" | "https" => Err(invalid("missing host")),
+        "file" if allow_file => Ok(()),
+        "file" => Err(invalid(
+            "file:// URLs are only allowed with GenerateOptions::allow_file_urls",
+        )),
+        _ => Err(invalid("scheme must be http or https")),
     }
+}
 
-    if platforms.is_empty() {
-        return Err(anyhow!(
-            "No platforms with valid signatures found. Cannot generate latest.json."
-        ));
+/// Everything but RFC 3986 unreserved characters is escaped in file names;
+/// `+` in particular is read as a space by some static hosts.
+pub(crate) const FILE_NAME_ENCODE_SET: &percent_encoding::AsciiSet =
+    &percent_encoding::NON_ALPHANUMERIC
+        .remove(b'-')
+        .remove(b'.')
+        .remove(b'_')
+        .remove(b'~');
+
+/// Builds the download URL for one installer.
+///
+/// `download_url_base` may contain `{{version}}`, `{{target}}` (the platform
+/// key), `{{arch}}`, and `{{file}}`; without placeholders the file name is
+/// appended after a single `/`. `file` may be a `/`-separated relative
+/// path; each segment is percent-encoded, the base is not.
+fn installer_url(download_url_base: &str, version: &str, platform: &str, file: &str) -> String {
+    const PLACEHOLDERS: [&str; 4] = ["{{version}}", "{{target}}", "{{arch}}", "{{file}}"];
+    let file = &url_path(file, std::path::MAIN_SEPARATOR)
+        .split('/')
+        .map(|segment| percent_encoding::utf8_percent_encode(segment, FILE_NAME_ENCODE_SET))
+        .map(|segment| segment.to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+    if !PLACEHOLDERS.iter().any(|p| download_url_base.contains(p)) {
+        return format!("{}/{}", download_url_base.trim_end_matches('/'), file);
     }
+    let arch = platform.split_once('-').map_or(platform, |(_, arch)| arch);
+    download_url_base
+        .replace("{{version}}", version)
+        .replace("{{target}}", platform)
+        .replace("{{arch}}", arch)
+        .replace("{{file}}", file)
+}
 
-    let latest_json = json!({
-        "version": version,
-        "notes": notes,
-        "pub_date": Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
-        "platforms": platforms
-    });
+/// Rewrites the host's path `separator` to `/`, so a relative path such as
+/// `nsis\App.exe` on Windows does not end up in a URL with backslashes.
+fn url_path(path: &str, separator: char) -> String {
+    path.replace(separator, "/")
+}
 
-    let output_path = project_dir.join("latest.json");
-    fs::write(&output_path, serde_json::to_string_pretty(&latest_json)?)
-        .context("failed to write latest.json")?;
-    println!(
-        "\n{} generated at {}",
-        "✔".green().bold(),
-        output_path.display().to_string().bold()
-    );
-    Ok(())
+/// The path of `installer` below `bundle_dir` as `/`-separated segments.
+fn relative_url_path(bundle_dir: &Path, installer: &Path) -> Option<String> {
+    let relative = installer.strip_prefix(bundle_dir).ok()?;
+    let segments = relative
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    Some(segments.join("/"))
 }
 
-fn read_public_key(conf_path: &Path) -> Result<String> {
-    let conf_str = fs::read_to_string(conf_path).context("failed to read tauri.conf.json")?;
-    let conf_json: Value =
-        serde_json::from_str(&conf_str).context("failed to parse tauri.conf.json")?;
-
-    // Try Tauri 2.0 path: plugins > updater > pubkey
-    if let Some(pubkey) = conf_json["plugins"]["updater"]["pubkey"].as_str() {
-        return Ok(pubkey.to_string());
-    }
-
-    // Try Tauri 1.0 path: tauri > updater > pubkey
-    if let Some(pubkey) = conf_json["tauri"]["updater"]["pubkey"].as_str() {
-        return Ok(pubkey.to_string());
-    }
-
-    Err(anyhow!("No public key found in tauri.conf.json (checked plugins.updater.pubkey and tauri.updater.pubkey)"))
-}
-
-fn detect_bundle_dir() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir().context("failed to get current directory")?;
-    let candidates = [
-        current_dir.join("target").join("release").join("bundle"),
-        current_dir
-            .join("src-tauri")
-            .join("target")
-            .join("release")
-            .join("bundle"),
-        current_dir
-            .join("..")
-            .join("src-tauri")
-            .join("target")
-            .join("release")
-            .join("bundle"),
-        current_dir.join("target").join("debug").join("bundle"),
-        current_dir
-            .join("src-tauri")
-            .join("target")
-            .join("debug")
-            .join("bundle"),
-    ];
-
-    for path in candidates {
-        if path.exists() {
-            return Ok(path);
+/// Folds an `existing` manifest into `latest`.
+///
+/// For the same version, platforms missing from `latest` are kept. For a
+/// different version, the previous release is pushed onto a `versions`
+/// array (newest first) so older clients can still find an update path.
+/// Either way, hand-added fields are carried forward (see
+/// [`carry_custom_fields`]).
+fn merge_latest_json(mut existing: LatestJson, mut latest: LatestJson) -> Result<LatestJson> {
+    carry_custom_fields(&existing, &mut latest);
+    if existing.version == latest.version {
+        for (platform, entry) in existing.platforms {
+            latest.platforms.entry(platform).or_insert(entry);
+        }
+        if let Some(versions) = existing.extra.remove("versions") {
+            latest.extra.insert("versions".to_string(), versions);
         }
+        return Ok(latest);
     }
 
-    Err(anyhow!(
-        "Could not detect bundle dir. Build your Tauri app to produce target/*/bundle."
-    ))
+    let mut versions = match existing.extra.remove("versions") {
+        Some(Value::Array(versions)) => versions,
+        _ => Vec::new(),
+    };
+    versions.insert(0, serde_json::to_value(existing)?);
+    versions.retain(|release| release["version"] != latest.version.as_str());
+    latest
+        .extra
+        .insert("versions".to_string(), Value::Array(versions));
+    Ok(latest)
 }
 
-fn find_installers(dir: &Path) -> Result<Vec<PathBuf>> {
-    let mut results = Vec::new();
-    for entry in walkdir::WalkDir::new(dir) {
-        let entry = entry.context("failed to read directory entry")?;
-        if entry.file_type().is_file() {
-            let fname = entry.file_name().to_string_lossy();
-            if fname.ends_with(".msi")
-                || fname.ends_with(".exe")
-                || fname.ends_with(".dmg")
-                || fname.ends_with(".AppImage")
-                || fname.ends_with(".deb")
-                || fname.ends_with(".rpm")
-                || fname.ends_with(".tar.gz")
-            {
-                results.push(entry.path().to_path_buf());
-            }
+/// Copies fields the crate does not manage (anything in `extra`, e.g. a
+/// hand-added `homepage`) from `existing` into `latest`, at the top level
+/// and into entries for the same platform key. Fields `latest` sets win;
+/// `versions` is left to the merge itself.
+fn carry_custom_fields(existing: &LatestJson, latest: &mut LatestJson) {
+    for (field, value) in &existing.extra {
+        if field != "versions" {
+            latest
+                .extra
+                .entry(field.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
+    for (platform, entry) in &mut latest.platforms {
+        let Some(old_entry) = existing.platforms.get(platform) else {
+            continue;
+        };
+        for (field, value) in &old_entry.extra {
+            entry
+                .extra
+                .entry(field.clone())
+                .or_insert_with(|| value.clone());
         }
     }
-    Ok(results)
 }
 
-fn installer_priority(platform: &str, filename: &str) -> u8 {
-    let lower = filename.to_ascii_lowercase();
-    match platform {
-        "windows-x86_64" => {
-            if lower.ends_with(".msi") {
-                30
-            } else if lower.ends_with(".exe") {
-                20
-            } else {
-                10
-            }
-        }
-        "darwin-aarch64" | "darwin-x86_64" => {
-            if lower.ends_with(".app.tar.gz") {
-                40
-            } else if lower.ends_with(".dmg") {
-                10
-            } else {
-                5
+/// Applies the output-related options (merge, dry run) before writing, and
+/// returns the manifest as written.
+pub(crate) fn write_latest_json_with_options(
+    latest_json: LatestJson,
+    output_path: &Path,
+    options: &GenerateOptions,
+) -> Result<LatestJson> {
+    let filesystem = options.filesystem.as_ref();
+    let stdout = options.writes_to_stdout();
+    // Per-platform files are written fresh; there is no single manifest to merge into.
+    let merge = options.merge_into_existing && options.output_mode == OutputMode::Single;
+    let existing = if merge && !stdout && filesystem.exists(output_path) {
+        let existing = filesystem.read(output_path).with_path(output_path)?;
+        Some(serde_json::from_slice(&existing).with_path(output_path)?)
+    } else {
+        None
+    };
+    let latest_json = finish_latest_json(latest_json, existing, options)?;
+    if options.dry_run {
+        // An unreadable live manifest only means there is nothing to compare.
+        let live = match !stdout && filesystem.exists(output_path) {
+            true => {
+                serde_json::from_slice(&filesystem.read(output_path).with_path(output_path)?).ok()
             }
+            false => None,
+        };
+        log_dry_run(&latest_json, live.as_ref(), output_path, options)?;
+        return Ok(latest_json);
+    }
+    if stdout {
+        write_manifest_to_stdout(&latest_json, options.pretty)?;
+        return Ok(latest_json);
+    }
+    if !options.overwrite {
+        let outputs = output_paths(&latest_json, output_path, options);
+        if let Some(path) = outputs.into_iter().find(|path| filesystem.exists(path)) {
+            return Err(LatestJsonError::OutputExists { path });
         }
-        "linux-aarch64" | "linux-x86_64" => {
-            if lower.ends_with(".appimage") {
-                40
-            } else if lower.ends_with(".deb") {
-                30
-            } else if lower.ends_with(".rpm") {
-                20
-            } else if lower.ends_with(".tar.gz") {
-                10
-            } else {
-                5
+    }
+    if let OutputMode::PerPlatform { file_name } = &options.output_mode {
+        for (path, manifest) in split::per_platform_outputs(&latest_json, output_path, file_name) {
+            if options.backup {
+                back_up_manifest(filesystem, &path)?;
             }
+            write_latest_json(filesystem, &manifest, &path, options.pretty)?;
         }
-        _ => 0,
+        return Ok(latest_json);
+    }
+    if options.backup {
+        back_up_manifest(filesystem, output_path)?;
     }
+    write_latest_json(filesystem, &latest_json, output_path, options.pretty)?;
+    Ok(latest_json)
 }
 
-fn find_installers_by_platform(dir: &Path) -> Result<HashMap<String, PathBuf>> {
-    let installers = find_installers(dir)?;
-    let mut selected: HashMap<String, (PathBuf, u8)> = HashMap::new();
+/// Copies the manifest at `path`, if any, to its [`backup_path_for`].
+fn back_up_manifest(filesystem: &dyn FileSystem, path: &Path) -> Result<()> {
+    if filesystem.exists(path) {
+        let backup_path = backup_path_for(path);
+        filesystem
+            .write(&backup_path, &filesystem.read(path).with_path(path)?)
+            .with_path(&backup_path)?;
+        log::info!("previous manifest backed up to {}", backup_path.display());
+    }
+    Ok(())
+}
 
-    for installer in installers {
-        let installer_name = match installer.file_name().and_then(|s| s.to_str()) {
-            Some(s) => s.to_string(),
-            None => continue,
-        };
-        let platform = detect_platform_key(&installer_name).to_string();
-        if platform == "unknown" {
-            continue;
-        }
-        let priority = installer_priority(&platform, &installer_name);
-        match selected.get(&platform) {
-            Some((_, existing_priority)) if *existing_priority >= priority => {}
-            _ => {
-                selected.insert(platform, (installer, priority));
-            }
+/// Every file a run writes `latest_json` to, for the overwrite check.
+pub(crate) fn output_paths(
+    latest_json: &LatestJson,
+    output_path: &Path,
+    options: &GenerateOptions,
+) -> Vec<PathBuf> {
+    match &options.output_mode {
+        OutputMode::PerPlatform { file_name } => {
+            split::per_platform_outputs(latest_json, output_path, file_name)
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect()
         }
+        OutputMode::Single => vec![output_path.to_path_buf()],
     }
+}
 
-    Ok(selected.into_iter().map(|(k, (v, _))| (k, v)).collect())
+/// Merges into `existing` (already read when merging is on) and checks the
+/// expected platforms; what is left is to write the result.
+pub(crate) fn finish_latest_json(
+    latest_json: LatestJson,
+    existing: Option<LatestJson>,
+    options: &GenerateOptions,
+) -> Result<LatestJson> {
+    let latest_json = match existing {
+        Some(existing) => merge_latest_json(existing, latest_json)?,
+        None => latest_json,
+    };
+    check_expected_platforms(&latest_json, options)?;
+    Ok(latest_json)
 }
 
-fn find_signatures(dir: &Path) -> Result<HashMap<String, PathBuf>> {
-    let mut results = HashMap::new();
-    for entry in walkdir::WalkDir::new(dir) {
-        let entry = entry.context("failed to read directory entry")?;
-        if entry.file_type().is_file() {
-            let fname = entry.file_name().to_string_lossy();
-            if fname.ends_with(".sig") {
-                let platform = detect_platform_key(&fname.replace(".sig", ""));
-                results.insert(platform.to_string(), entry.path().to_path_buf());
-            }
-        }
+/// The release channel of `version`: its first pre-release identifier
+/// (`beta` for `1.2.0-beta.3`), or `None` for a stable or non-semver version.
+pub(crate) fn release_channel(version: &str) -> Option<String> {
+    let version = semver::Version::parse(version).ok()?;
+    let channel = version.pre.as_str().split('.').next()?;
+    (!channel.is_empty()).then(|| channel.to_string())
+}
+
+/// Where a channel-aware run writes `version`: `channels[channel]` when
+/// given, else `output` with `-<channel>` added to its stem. Stable
+/// versions keep `output`.
+pub(crate) fn channel_output_path(
+    output: &Path,
+    version: &str,
+    channels: &HashMap<String, PathBuf>,
+) -> PathBuf {
+    let Some(channel) = release_channel(version) else {
+        return output.to_path_buf();
+    };
+    log::info!("{} is a {} release", version, channel);
+    if let Some(path) = channels.get(&channel) {
+        return path.clone();
     }
-    Ok(results)
+    let stem = output
+        .file_stem()
+        .map_or_else(|| "latest".into(), |stem| stem.to_string_lossy());
+    let file_name = match output.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, channel, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, channel),
+    };
+    output.with_file_name(file_name)
 }
 
-fn detect_tauri_conf_path() -> Result<PathBuf> {
-    let current_dir = std::env::current_dir().context("failed to get current directory")?;
-    let candidates = [
-        current_dir.join("tauri.conf.json"),
-        current_dir.join("src-tauri").join("tauri.conf.json"),
-        current_dir
-            .join("..")
-            .join("src-tauri")
-            .join("tauri.conf.json"),
-    ];
-    for c in candidates {
-        if c.exists() {
-            return Ok(c);
+/// Where [`GenerateOptions::backup`] keeps the previous manifest:
+/// `latest.json` becomes `latest.json.bak`.
+pub(crate) fn backup_path_for(output_path: &Path) -> PathBuf {
+    let mut file_name = output_path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "latest.json".into());
+    file_name.push(".bak");
+    output_path.with_file_name(file_name)
+}
+
+/// Logs the manifest a dry run would write and, when `live` (the manifest
+/// currently at the output path) is known, what it would change.
+pub(crate) fn log_dry_run(
+    latest_json: &LatestJson,
+    live: Option<&LatestJson>,
+    output_path: &Path,
+    options: &GenerateOptions,
+) -> Result<()> {
+    log::info!(
+        "dry run, not writing {}:\n{}",
+        output_path.display(),
+        manifest_to_string(latest_json, options.pretty)?
+    );
+    if let Some(live) = live {
+        let diff = diff_manifests(live, latest_json);
+        if diff.is_empty() {
+            log::info!("no changes to {}", output_path.display());
+        } else {
+            log::info!("changes to {}:\n{}", output_path.display(), diff);
         }
     }
-    Err(anyhow!(
-        "Could not find tauri.conf.json. Provide it at project root or src-tauri/."
-    ))
+    Ok(())
 }
 
-#[cfg(feature = "verify-signature")]
-fn verify_signature(installer: &Path, signature: &str, public_key: &str) -> Result<()> {
-    let installer_str = installer
-        .to_str()
-        .ok_or_else(|| anyhow!("Installer path is not valid UTF-8"))?;
-    let output = Command::new("tauri")
-        .args([
-            "signer",
-            "verify",
-            "--public-key",
-            public_key,
-            installer_str,
-            signature,
-        ])
-        .output()
-        .context("failed to execute tauri signer verify")?;
-
-    if !output.status.success() {
-        return Err(anyhow!(
-            "Signature verification failed for {:?}: {}",
-            installer,
-            String::from_utf8_lossy(&output.stderr)
-        ));
+/// Fails (or warns, with `allow_missing_platforms`) when a platform from
+/// `expect_platforms` is not in the manifest.
+fn check_expected_platforms(latest_json: &LatestJson, options: &GenerateOptions) -> Result<()> {
+    let missing: Vec<String> = options
+        .expect_platforms
+        .iter()
+        .filter(|key| !latest_json.platforms.contains_key(key.as_str()))
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
     }
+    if options.allow_missing_platforms {
+        log::warn!(
+            "expected platforms missing from manifest: {}",
+            missing.join(", ")
+        );
+        return Ok(());
+    }
+    Err(LatestJsonError::MissingExpectedPlatform { missing })
+}
+
+/// Serializes the manifest indented, or compact when `pretty` is false.
+pub(crate) fn manifest_to_string(latest_json: &LatestJson, pretty: bool) -> Result<String> {
+    Ok(if pretty {
+        serde_json::to_string_pretty(latest_json)?
+    } else {
+        serde_json::to_string(latest_json)?
+    })
+}
 
+/// Writes the manifest to stdout, exactly as it would be written to a file.
+pub(crate) fn write_manifest_to_stdout(latest_json: &LatestJson, pretty: bool) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(manifest_to_string(latest_json, pretty)?.as_bytes())?;
+    stdout.flush()?;
     Ok(())
 }
 
-fn detect_platform_key(filename: &str) -> &'static str {
-    let lower = filename.to_ascii_lowercase();
-    if lower.ends_with(".msi") || lower.ends_with(".exe") {
-        "windows-x86_64"
-    } else if lower.ends_with(".app.tar.gz") || lower.ends_with(".dmg") {
-        if lower.contains("aarch64") || lower.contains("arm64") {
-            "darwin-aarch64"
-        } else {
-            "darwin-x86_64"
+/// Writes a built manifest to `output_path`, creating parent directories.
+fn write_latest_json(
+    fs: &dyn FileSystem,
+    latest_json: &LatestJson,
+    output_path: &Path,
+    pretty: bool,
+) -> Result<()> {
+    fs.write(
+        output_path,
+        manifest_to_string(latest_json, pretty)?.as_bytes(),
+    )?;
+    log::info!("latest.json generated at {}", output_path.display());
+    Ok(())
+}
+
+pub(crate) fn build_latest_json_for_project(
+    bundle_dir: &Path,
+    public_key: &str,
+    project_dir: &Path,
+    options: &GenerateOptions,
+    mapper: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<LatestJson> {
+    build_latest_json_with_summary(
+        bundle_dir,
+        &[public_key.to_string()],
+        project_dir,
+        options,
+        mapper,
+    )
+    .map(|(manifest, _)| manifest)
+}
+
+pub(crate) fn build_latest_json_with_summary(
+    bundle_dir: &Path,
+    public_keys: &[String],
+    project_dir: &Path,
+    options: &GenerateOptions,
+    mapper: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<(LatestJson, GenerationSummary)> {
+    let download_url_base = options.download_url_base.as_str();
+    let (version, version_file) = match &options.version {
+        Some(version) => (version.clone(), None),
+        None => match read_version_with_file(
+            project_dir,
+            options.version_source,
+            options.package_json_lookup,
+        ) {
+            Err(LatestJsonError::VersionNotFound) if options.git_fallback && options.offline => {
+                log::warn!("offline mode does not run git, skipping the git tag fallback");
+                return Err(LatestJsonError::VersionNotFound);
+            }
+            Err(LatestJsonError::VersionNotFound) if options.git_fallback => {
+                (read_version_from_git(project_dir)?, None)
+            }
+            result => result.map(|(version, file)| (version, Some(file)))?,
+        },
+    };
+    let version = normalize_version(&version, options.strict_semver)?;
+    match &version_file {
+        Some(file) => log::info!("detected version: {} (from {})", version, file.display()),
+        None => log::info!("detected version: {}", version),
+    }
+    let minimum_version = options
+        .minimum_version
+        .as_deref()
+        .map(|minimum| check_minimum_version(minimum, &version))
+        .transpose()?;
+    let notes = notes::resolve_notes(options, &version)?;
+    let notes = notes.as_str();
+
+    validate_download_url(download_url_base, options.allow_file_urls)?;
+    for url in options.url_overrides.values() {
+        validate_download_url(url, options.allow_file_urls)?;
+    }
+
+    let filesystem = RetryingFs::new(options.filesystem.as_ref(), options.read_retries);
+    let filesystem: &dyn FileSystem = &filesystem;
+    let all_installers = match &options.installer_extensions {
+        Some(extensions) => installers_in(filesystem, bundle_dir, extensions, options.walk)?,
+        None => {
+            let extensions = match options.manifest_format {
+                ManifestFormat::V1 => V1_INSTALLER_EXTENSIONS,
+                ManifestFormat::V2 => DEFAULT_INSTALLER_EXTENSIONS,
+            };
+            installers_in(filesystem, bundle_dir, extensions, options.walk)?
         }
-    } else if lower.ends_with(".appimage")
-        || lower.ends_with(".deb")
-        || lower.ends_with(".rpm")
-        || lower.ends_with(".tar.gz")
-    {
-        if lower.contains("aarch64") || lower.contains("arm64") {
-            "linux-aarch64"
-        } else {
-            "linux-x86_64"
+    };
+    let all_installers: Vec<PathBuf> = match &options.app_name {
+        Some(app_name) => all_installers
+            .into_iter()
+            .filter(|path| is_app_installer(&file_name_lossy(path), app_name))
+            .collect(),
+        None => all_installers,
+    };
+    let installer_count = all_installers.len();
+    let discovered = all_installers.clone();
+    let mut warnings = Vec::new();
+    let all_installers = check_installer_versions(
+        all_installers,
+        &version,
+        options.version_filter,
+        &mut warnings,
+    )?;
+    // Strict signing covers installers that lose selection too.
+    let candidates = match options.require_all_signed {
+        true => all_installers.clone(),
+        false => Vec::new(),
+    };
+    let installers = select_installers_by_platform(
+        all_installers,
+        mapper,
+        options.windows_installer,
+        &mut warnings,
+    );
+    if options.strict_platforms {
+        if let Some(Warning::UnknownPlatform { installer }) = warnings
+            .iter()
+            .find(|warning| matches!(warning, Warning::UnknownPlatform { .. }))
+        {
+            return Err(LatestJsonError::UnknownPlatform {
+                installer: installer.clone(),
+            });
         }
-    } else {
-        "unknown"
     }
-}
+    if installers.is_empty() {
+        return Err(LatestJsonError::NoInstallers {
+            bundle_dir: bundle_dir.to_path_buf(),
+        });
+    }
+
+    let signature_paths = find_signatures(
+        filesystem,
+        bundle_dir,
+        options.walk,
+        &options.signature_extension,
+    )?;
+    let mut orphans: Vec<&PathBuf> = signature_paths
+        .iter()
+        .filter(|(installer, _)| !filesystem.exists(installer))
+        .map(|(_, sig_path)| sig_path)
+        .collect();
+    orphans.sort();
+    warnings.extend(
+        orphans
+            .into_iter()
+            .map(|sig_path| Warning::OrphanSignature {
+                signature: file_name_lossy(sig_path),
+            }),
+    );
+    let selected: HashMap<PathBuf, String> = installers
+        .iter()
+        .map(|(platform_key, installer)| (installer.clone(), platform_key.clone()))
+        .collect();
+    let mut signed = Vec::new();
+    let mut unsigned = Vec::new();
+    // Unselected installers can only be signed by a signature file; the
+    // selected ones are checked in the loop, where the variable counts too.
+    let mut missing: Vec<PathBuf> = candidates
+        .into_iter()
+        .filter(|path| !selected.contains_key(path) && !signature_paths.contains_key(path))
+        .collect();
+    for (platform_key, installer) in installers {
+        // A lossy name would put a URL in the manifest that names no file.
+        let Some(installer_name) = installer
+            .file_name()
+            .and_then(|s| s.to_str().map(|s| s.to_string()))
+        else {
+            return Err(LatestJsonError::NonUtf8FileName { path: installer });
+        };
+
+        let sig_source = match signature_paths.get(&installer) {
+            Some(sig_path) => Some(SignatureSource::File(sig_path.clone())),
+            // A DMG is not an updater artifact, so the platform's variable is not for it.
+            None if installer_name.ends_with(".dmg") => None,
+            None => env_signature(&platform_key).map(|signature| {
+                log::debug!(
+                    "using signature for {} from {}",
+                    installer_name,
+                    signature_env_var(&platform_key)
+                );
+                SignatureSource::Env(signature)
+            }),
+        };
+        let sig_source = match sig_source {
+            Some(source) => source,
+            None if options.require_all_signed => {
+                missing.push(installer);
+                continue;
+            }
+            None if installer_name.ends_with(".dmg") || options.verify == VerifyMode::Off => {
+                log::debug!("no signature for {}, skipping", installer_name);
+                warnings.push(Warning::UnsignedInstaller {
+                    platform: platform_key,
+                    installer: installer_name.clone(),
+                });
+                unsigned.push(installer_name);
+                continue;
+            }
+            None => {
+                if let Some(archive) =
+                    updater_archive(&installer).filter(|a| signature_paths.contains_key(a))
+                {
+                    log::warn!(
+                        "{} is unsigned but {} is signed; use ManifestFormat::V1 (--manifest-format v1) for zipped updater artifacts",
+                        installer_name,
+                        file_name_lossy(&archive)
+                    );
+                }
+                return Err(LatestJsonError::MissingSignature {
+                    platform: platform_key,
+                    installer,
+                });
+            }
+        };
+        let url_path = if options.preserve_relative_path {
+            relative_url_path(bundle_dir, &installer).unwrap_or_else(|| installer_name.clone())
+        } else {
+            installer_name.clone()
+        };
+        signed.push((
+            platform_key,
+            installer,
+            installer_name,
+            url_path,
+            sig_source,
+        ));
+    }
+    if !missing.is_empty() {
+        missing.sort();
+        return Err(LatestJsonError::UnsignedInstallers { paths: missing });
+    }
+
+    // Reading and verifying hashes each installer, so do it in parallel.
+    let platforms = signed
+        .into_par_iter()
+        .map(
+            |(platform_key, installer, installer_name, url_path, sig_source)| {
+                options.emit_progress(ProgressEvent::StartedInstaller {
+                    name: installer_name.clone(),
+                });
+                let signature = match sig_source {
+                    SignatureSource::File(sig_path) => {
+                        String::from_utf8_lossy(&filesystem.read(&sig_path).with_path(&sig_path)?)
+                            .into_owned()
+                    }
+                    SignatureSource::Env(signature) => signature,
+                };
+                // Editors may add a BOM or newline; the updater rejects either.
+                let signature = signature.trim_start_matches('\u{feff}').trim();
+                if signature.is_empty() && options.verify != VerifyMode::Off {
+                    return Err(LatestJsonError::EmptySignature {
+                        platform: platform_key,
+                        installer,
+                    });
+                }
+
+                if options.verify == VerifyMode::Full {
+                    crypto::verify_installer(
+                        options.verifier.as_ref(),
+                        filesystem,
+                        &installer,
+                        signature,
+                        public_keys,
+                    )?;
+                }
+
+                log::info!("matched platform {}: {}", platform_key, installer_name);
+
+                let platform_notes = (!options.platform_notes.is_empty()).then(|| {
+                    options
+                        .platform_notes
+                        .get(&platform_key)
+                        .map_or_else(|| notes.to_string(), String::clone)
+                });
+                let sha256 = if options.include_checksums {
+                    Some(sha256_file(filesystem, &installer)?)
+                } else {
+                    None
+                };
+                let size = if options.include_size {
+                    Some(filesystem.file_size(&installer)?)
+                } else {
+                    None
+                };
+                let entry = PlatformEntry {
+                    signature: signature.to_string(),
+                    url: match options.url_overrides.get(&platform_key) {
+                        Some(url) => url.clone(),
+                        None => {
+                            installer_url(download_url_base, &version, &platform_key, &url_path)
+                        }
+                    },
+                    notes: platform_notes,
+                    sha256,
+                    size,
+                    extra: Default::default(),
+                };
+                let name = installer_name;
+                options.emit_progress(match options.verify {
+                    VerifyMode::Full => ProgressEvent::Verified { name },
+                    VerifyMode::PresenceOnly | VerifyMode::Off => ProgressEvent::Processed { name },
+                });
+                Ok((platform_key, entry))
+            },
+        )
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    if platforms.is_empty() {
+        return Err(LatestJsonError::NoSignedPlatforms);
+    }
+    options.emit_progress(ProgressEvent::Finished {
+        count: platforms.len(),
+    });
+
+    let installers = discovered
+        .into_iter()
+        .map(|path| {
+            let platform_key = selected
+                .get(&path)
+                .filter(|platform_key| platforms.contains_key(*platform_key))
+                .cloned();
+            let signature = match platform_key {
+                Some(_) if options.verify == VerifyMode::Full => SignatureStatus::Verified,
+                Some(_) => SignatureStatus::Present,
+                None if signature_paths.contains_key(&path) => SignatureStatus::Present,
+                None => SignatureStatus::Missing,
+            };
+            InstallerStatus {
+                size: filesystem.file_size(&path).ok(),
+                path,
+                platform_key,
+                signature,
+            }
+        })
+        .collect();
+    let summary = GenerationSummary {
+        version: version.clone(),
+        version_file,
+        platform_keys: platforms.keys().cloned().collect(),
+        installer_count,
+        installers,
+        unsigned,
+        warnings,
+    };
+    let manifest = LatestJson {
+        version,
+        notes: notes.to_string(),
+        pub_date: clock::format_pub_date(&options.clock.now(), &options.pub_date_format)?,
+        platforms,
+        critical: options.critical,
+        minimum_version,
+        extra: Default::default(),
+    };
+    if options.validate {
+        manifest::validate_manifest_with(
+            &manifest,
+            manifest::ManifestChecks {
+                version: options.strict_semver,
+                pub_date: !matches!(options.pub_date_format, PubDateFormat::Custom(_)),
+                signatures: options.verify != VerifyMode::Off,
+            },
+        )?;
+    }
+    Ok((manifest, summary))
+}
+
+/// Environment variable holding the updater public key (base64, as it would
+/// appear in `tauri.conf.json`).
+pub const PUBLIC_KEY_ENV: &str = "TAURI_SIGNING_PUBLIC_KEY";
+
+/// Resolves the updater public key for auto mode.
+///
+/// Precedence: an explicit `public_key` passed to [`generate_latest_json`]
+/// wins outright, then a `.pub` file at `public_key_path`, then
+/// [`PUBLIC_KEY_ENV`] when set and non-empty, and finally the key from
+/// `tauri.conf.json` (given or auto-detected), which may itself name a key
+/// file. The config is only located when nothing earlier provides a key.
+/// Only the config can supply several keys (see [`read_public_key`]).
+pub(crate) fn resolve_public_key(
+    public_key_path: Option<&Path>,
+    tauri_conf_path: Option<&Path>,
+) -> Result<Vec<String>> {
+    if let Some(path) = public_key_path {
+        log::debug!("using public key file {}", path.display());
+        return Ok(vec![read_public_key_file(path)?]);
+    }
+    if let Some(pubkey) = public_key_from_env() {
+        log::debug!("using public key from {}", PUBLIC_KEY_ENV);
+        return Ok(vec![pubkey]);
+    }
+    let tauri_conf_path = match tauri_conf_path {
+        Some(path) => path.to_path_buf(),
+        None => detect_tauri_conf_path()?,
+    };
+    log::debug!("reading public key from {}", tauri_conf_path.display());
+    read_public_key(&tauri_conf_path)
+}
+
+fn public_key_from_env() -> Option<String> {
+    std::env::var(PUBLIC_KEY_ENV)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Reads an updater public key (the base64 blob `tauri signer generate`
+/// writes to `*.key.pub`) from `path`. A leading `~/` is expanded to the
+/// home directory.
+pub fn read_public_key_file(path: &Path) -> Result<String> {
+    let path = expand_home(path);
+    let fail = |reason: String| LatestJsonError::PublicKeyFile {
+        path: path.clone(),
+        reason,
+    };
+    let pubkey = fs::read_to_string(&path).map_err(|e| fail(e.to_string()))?;
+    let pubkey = pubkey.trim();
+    if pubkey.is_empty() {
+        return Err(fail("file is empty".to_string()));
+    }
+    Ok(pubkey.to_string())
+}
+
+/// Checks that `public_key` is a minisign public key in one of the forms
+/// Tauri accepts: the base64-wrapped `.pub` file text (what
+/// `tauri signer generate` prints) or the bare `RW...` key line. Resolved
+/// keys are checked before generation so a truncated key fails fast.
+///
+/// ```
+/// assert!(tauri_latest_json::validate_public_key("dW50cnVzdGVk").is_err());
+/// ```
+pub fn validate_public_key(public_key: &str) -> Result<()> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let invalid = |reason: &str| LatestJsonError::InvalidPublicKey(reason.to_string());
+    let decoded = STANDARD
+        .decode(public_key.trim())
+        .map_err(|_| invalid("not valid base64"))?;
+    if is_minisign_key(&decoded) {
+        return Ok(());
+    }
+    let text = String::from_utf8(decoded).map_err(|_| invalid("not a minisign public key"))?;
+    let key_line = text
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty() && !line.starts_with("untrusted comment:"))
+        .ok_or_else(|| invalid("the decoded text has no key line"))?;
+    match STANDARD.decode(key_line) {
+        Ok(key) if is_minisign_key(&key) => Ok(()),
+        Ok(_) => Err(invalid(
+            "the key line is not an Ed25519 minisign key (truncated?)",
+        )),
+        Err(_) => Err(invalid("the key line is not valid base64")),
+    }
+}
+
+/// A minisign public key: the `Ed` algorithm tag, an 8-byte key id, and a
+/// 32-byte Ed25519 key.
+fn is_minisign_key(bytes: &[u8]) -> bool {
+    bytes.len() == 42 && bytes.starts_with(b"Ed")
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Config file names Tauri accepts, in lookup order.
+const TAURI_CONF_FILE_NAMES: [&str; 2] = ["tauri.conf.json", "tauri.conf.json5"];
+
+/// Parses a Tauri config, accepting JSON5 for `.json5` files and tolerating
+/// comments in `.json` files.
+fn read_tauri_conf(conf_path: &Path) -> Result<Value> {
+    let conf_str = fs::read_to_string(conf_path).with_path(conf_path)?;
+    if conf_path.extension().is_some_and(|ext| ext == "json5") {
+        return json5::from_str(&conf_str).with_path(conf_path);
+    }
+    match serde_json::from_str(&conf_str) {
+        Ok(conf_json) => Ok(conf_json),
+        // Plain JSON failed; retry as JSON5 in case it only has comments,
+        // but report the original error if that fails too.
+        Err(e) => json5::from_str(&conf_str)
+            .map_err(|_| e)
+            .with_path(conf_path),
+    }
+}
+
+/// Reads the updater public keys from the config at `conf_path`: `pubkey`,
+/// then any in an optional `pubkeys` array next to it, for verifying
+/// against the old and new key during a rotation. A value naming an
+/// existing file (relative to the config's directory) is read as a key
+/// file; anything else is the key itself.
+fn read_public_key(conf_path: &Path) -> Result<Vec<String>> {
+    let conf_dir = conf_path.parent().unwrap_or_else(|| Path::new(""));
+    read_config_pubkeys(conf_path)?
+        .into_iter()
+        .map(|pubkey| {
+            let key_file = conf_dir.join(expand_home(Path::new(&pubkey)));
+            if key_file.is_file() {
+                log::debug!(
+                    "using public key file {} named in {}",
+                    key_file.display(),
+                    conf_path.display()
+                );
+                return read_public_key_file(&key_file);
+            }
+            log::debug!("using inline public key from {}", conf_path.display());
+            Ok(pubkey)
+        })
+        .collect()
+}
+
+fn read_config_pubkeys(conf_path: &Path) -> Result<Vec<String>> {
+    let conf_json = read_tauri_conf(conf_path)?;
+
+    // Tauri 1 keeps app settings under a top-level `tauri` object (and may
+    // also have an unrelated `plugins`); Tauri 2 moved the updater to
+    // `plugins`. Look where the detected schema puts the key first, then at
+    // the other location for half-migrated configs.
+    let (schema, paths) = if conf_json["tauri"].is_object() {
+        (
+            "v1",
+            [
+                ["tauri", "updater", "pubkey"],
+                ["plugins", "updater", "pubkey"],
+            ],
+        )
+    } else {
+        (
+            "v2",
+            [
+                ["plugins", "updater", "pubkey"],
+                ["tauri", "updater", "pubkey"],
+            ],
+        )
+    };
+    log::debug!(
+        "{} looks like a Tauri {} config",
+        conf_path.display(),
+        schema
+    );
+
+    let mut pubkeys = Vec::new();
+    if let Some(pubkey) = paths
+        .iter()
+        .filter_map(|[section, plugin, key]| conf_json[section][plugin][key].as_str())
+        .find(|pubkey| !pubkey.is_empty())
+    {
+        pubkeys.push(pubkey.to_string());
+    }
+    for [section, plugin, _] in paths {
+        let extra = conf_json[section][plugin]["pubkeys"].as_array();
+        for pubkey in extra.into_iter().flatten().filter_map(|v| v.as_str()) {
+            if !pubkey.is_empty() && !pubkeys.iter().any(|known| known == pubkey) {
+                pubkeys.push(pubkey.to_string());
+            }
+        }
+    }
+    if !pubkeys.is_empty() {
+        return Ok(pubkeys);
+    }
+
+    // Say whether the plugin or just its key is missing.
+    if paths
+        .iter()
+        .any(|[section, plugin, _]| conf_json[section][plugin].is_object())
+    {
+        Err(LatestJsonError::PublicKeyMissing)
+    } else {
+        Err(LatestJsonError::UpdaterNotConfigured)
+    }
+}
+
+/// Environment variable naming the bundle directory directly, for builds
+/// whose artifacts are copied out of `target/` before the manifest step.
+pub const BUNDLE_DIR_ENV: &str = "TAURI_BUNDLE_DIR";
+
+/// Finds the bundle dir for `profile`, or for `release` then `debug` when
+/// no profile is given.
+pub(crate) fn detect_bundle_dir(profile: Option<&str>) -> Result<PathBuf> {
+    let current_dir = std::env::current_dir()?;
+    let reported_profile = profile.unwrap_or("release").to_string();
+    if let Some(dir) = std::env::var_os(BUNDLE_DIR_ENV).filter(|v| !v.is_empty()) {
+        let dir = current_dir.join(dir);
+        if !dir.is_dir() {
+            return Err(LatestJsonError::BundleDirNotFound {
+                searched: vec![dir],
+                profile: reported_profile,
+            });
+        }
+        log::debug!("using bundle dir {} from {}", dir.display(), BUNDLE_DIR_ENV);
+        return Ok(dir);
+    }
+    let cargo_target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .filter(|v| !v.is_empty())
+        .map(|v| current_dir.join(v));
+    let profiles = match profile {
+        Some(profile) => vec![profile],
+        None => vec!["release", "debug"],
+    };
+    let candidates = bundle_dir_candidates(&current_dir, cargo_target_dir.as_deref(), &profiles);
+
+    for path in &candidates {
+        if path.exists() {
+            log::debug!("using bundle dir {}", path.display());
+            return Ok(path.clone());
+        }
+    }
+
+    Err(LatestJsonError::BundleDirNotFound {
+        searched: candidates,
+        profile: reported_profile,
+    })
+}
+
+/// Lists bundle dir candidates in lookup order.
+///
+/// `CARGO_TARGET_DIR` is authoritative when set. Otherwise the crate-local
+/// target dirs are tried, followed by the target dir of the enclosing Cargo
+/// workspace, for each of `profiles` in order.
+fn bundle_dir_candidates(
+    current_dir: &Path,
+    cargo_target_dir: Option<&Path>,
+    profiles: &[&str],
+) -> Vec<PathBuf> {
+    let target_dirs = match cargo_target_dir {
+        Some(dir) => vec![dir.to_path_buf()],
+        None => {
+            let mut dirs = vec![
+                current_dir.join("target"),
+                current_dir.join("src-tauri").join("target"),
+                current_dir.join("..").join("src-tauri").join("target"),
+            ];
+            if let Some(root) = find_workspace_root(current_dir) {
+                let workspace_target = root.join("target");
+                if !dirs.contains(&workspace_target) {
+                    dirs.push(workspace_target);
+                }
+            }
+            dirs
+        }
+    };
+
+    profiles
+        .iter()
+        .flat_map(|profile| {
+            target_dirs
+                .iter()
+                .map(move |dir| dir.join(profile).join("bundle"))
+        })
+        .collect()
+}
+
+/// Walks up from `start` to the nearest `Cargo.toml` declaring `[workspace]`.
+fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    start.ancestors().find_map(|dir| {
+        let cargo_str = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+        let value: toml::Value = toml::from_str(&cargo_str).ok()?;
+        value.get("workspace").map(|_| dir.to_path_buf())
+    })
+}
+
+/// File extensions treated as installers by default.
+pub const DEFAULT_INSTALLER_EXTENSIONS: &[&str] =
+    &["msi", "exe", "dmg", "AppImage", "deb", "rpm", "tar.gz"];
+
+/// The updater archives [`ManifestFormat::V1`] looks for instead.
+pub const V1_INSTALLER_EXTENSIONS: &[&str] =
+    &["msi.zip", "nsis.zip", "app.tar.gz", "AppImage.tar.gz"];
+
+/// Recursively lists every installer under `dir` (`.msi`, `.exe`, `.dmg`,
+/// `.AppImage`, `.deb`, `.rpm`, `.tar.gz`), including ones the updater does
+/// not use such as `.dmg`. Discovery follows [`WalkOptions::default`]: four
+/// levels deep, skipping hidden directories.
+pub fn find_installers(dir: &Path) -> Result<Vec<PathBuf>> {
+    find_installers_with_extensions(dir, DEFAULT_INSTALLER_EXTENSIONS)
+}
+
+/// Like [`find_installers`], but only files ending in one of `extensions`
+/// count as installers. A leading `.` is optional and matching ignores ASCII
+/// case, so `["app.tar.gz", ".zip"]` is fine.
+pub fn find_installers_with_extensions<S: AsRef<str>>(
+    dir: &Path,
+    extensions: &[S],
+) -> Result<Vec<PathBuf>> {
+    installers_in(&RealFs, dir, extensions, WalkOptions::default())
+}
+
+/// Lists what generation would see in `bundle_dir` without building or
+/// writing anything: every installer, the platform key its name maps to,
+/// and its `.sig`, if any. Useful for finding out why a platform is missing
+/// or mislabeled. Installers are sorted by path; when several share a
+/// platform key, generation picks only one of them.
+pub fn inspect_bundle(bundle_dir: &Path) -> Result<Vec<InstallerInfo>> {
+    let mut installers = find_installers(bundle_dir)?;
+    installers.sort();
+    let mut signatures = find_signatures(
+        &RealFs,
+        bundle_dir,
+        WalkOptions::default(),
+        DEFAULT_SIGNATURE_EXTENSION,
+    )?;
+    Ok(installers
+        .into_iter()
+        .map(|path| InstallerInfo {
+            platform_key: detect_platform_key(&file_name_lossy(&path))
+                .map_or_else(|| "unknown".to_string(), |target| target.key()),
+            signature_path: signatures.remove(&path),
+            path,
+        })
+        .collect())
+}
+
+/// Runs [`inspect_bundle`] on the auto-detected bundle directory.
+pub fn inspect_bundle_auto() -> Result<Vec<InstallerInfo>> {
+    inspect_bundle(&detect_bundle_dir(None)?)
+}
+
+fn installers_in<S: AsRef<str>>(
+    fs: &dyn FileSystem,
+    dir: &Path,
+    extensions: &[S],
+    walk: WalkOptions,
+) -> Result<Vec<PathBuf>> {
+    let suffixes: Vec<String> = extensions
+        .iter()
+        .map(|ext| format!(".{}", ext.as_ref().trim_start_matches('.')).to_ascii_lowercase())
+        .collect();
+    Ok(fs
+        .walk(dir, walk)?
+        .into_iter()
+        .filter(|path| {
+            let fname = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_ascii_lowercase();
+            suffixes
+                .iter()
+                .any(|suffix| fname.ends_with(suffix.as_str()))
+        })
+        .collect())
+}
+
+/// Whether `filename` belongs to the product `app_name`: it starts with the
+/// name (ignoring ASCII case) followed by `_` or `.`, as in
+/// `MyApp_1.0.0_x64_en-US.msi` or `MyApp.app.tar.gz`, so `MyApp` does not
+/// claim `MyAppAdmin_1.0.0_x64_en-US.msi`.
+fn is_app_installer(filename: &str, app_name: &str) -> bool {
+    filename
+        .get(..app_name.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(app_name))
+        && matches!(filename[app_name.len()..].chars().next(), Some('_' | '.'))
+}
+
+/// How generation treats installer signatures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VerifyMode {
+    /// Require a non-empty `.sig` and check it against the public key with
+    /// [`MinisignVerifier`](crate::MinisignVerifier) (the `verify-signature`
+    /// feature) or the verifier given to [`GenerateOptions::verifier`].
+    /// Without either, every check fails.
+    Full,
+    /// Require a non-empty `.sig` for every selected installer but skip the
+    /// cryptographic check, e.g. for a fast CI gate that verifies later.
+    /// The default with every feature set, so enabling `verify-signature`
+    /// does not change what a run requires.
+    #[default]
+    PresenceOnly,
+    /// Do not require signatures: installers without a `.sig` are left out
+    /// with a [`Warning::UnsignedInstaller`] instead of failing.
+    Off,
+}
+
+/// Which Windows installer becomes the updater entry when a build produces
+/// both an MSI and an NSIS `.exe` for the same platform.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowsInstaller {
+    /// The NSIS `-setup.exe`.
+    Nsis,
+    /// The WiX `.msi`.
+    #[default]
+    Msi,
+}
+
+/// Which Tauri updater generation the manifest targets.
+///
+/// The JSON is the same for both: `version`, `notes`, `pub_date`, and
+/// `platforms[key].{signature,url}`. What differs is the artifact each
+/// entry points at. Tauri 2 updates from the installers themselves (`.msi`,
+/// NSIS `.exe`, `.AppImage`, `.app.tar.gz`); Tauri 1, and Tauri 2 with
+/// `createUpdaterArtifacts: "v1Compatible"`, updates from archives around
+/// them (`.msi.zip`, `.nsis.zip`, `.AppImage.tar.gz`, `.app.tar.gz`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ManifestFormat {
+    /// Point entries at the v1 updater archives
+    /// ([`V1_INSTALLER_EXTENSIONS`]).
+    V1,
+    /// Point entries at the installers ([`DEFAULT_INSTALLER_EXTENSIONS`]).
+    #[default]
+    V2,
+}
+
+/// The v1 updater archive Tauri builds next to `installer`, e.g.
+/// `App_x64-setup.nsis.zip` for `App_x64-setup.exe`.
+fn updater_archive(installer: &Path) -> Option<PathBuf> {
+    let name = installer.file_name()?.to_str()?;
+    let archive = if let Some(stem) = name.strip_suffix(".exe") {
+        format!("{}.nsis.zip", stem)
+    } else if name.ends_with(".msi") {
+        format!("{}.zip", name)
+    } else if name.ends_with(".AppImage") {
+        format!("{}.tar.gz", name)
+    } else {
+        return None;
+    };
+    Some(installer.with_file_name(archive))
+}
+
+fn installer_priority(platform: &str, filename: &str, windows: WindowsInstaller) -> u8 {
+    let lower = filename.to_ascii_lowercase();
+    match platform.split('-').next().unwrap_or_default() {
+        "windows" => {
+            let (msi, exe) = match windows {
+                WindowsInstaller::Msi => (30, 20),
+                WindowsInstaller::Nsis => (20, 30),
+            };
+            if lower.ends_with(".msi") || lower.ends_with(".msi.zip") {
+                msi
+            } else if lower.ends_with(".exe") || lower.ends_with(".nsis.zip") {
+                exe
+            } else {
+                10
+            }
+        }
+        "darwin" => {
+            if lower.ends_with(".app.tar.gz") {
+                40
+            } else if lower.ends_with(".dmg") {
+                10
+            } else {
+                5
+            }
+        }
+        "linux" => {
+            if lower.ends_with(".appimage") {
+                40
+            } else if lower.ends_with(".deb") {
+                30
+            } else if lower.ends_with(".rpm") {
+                20
+            } else if lower.ends_with(".tar.gz") {
+                10
+            } else {
+                5
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// Keeps the highest-priority installer for each platform. Installers are
+/// visited in path order so ties resolve the same way on every run, and
+/// each platform's skipped candidates are logged. Unrecognized files and
+/// ties between equally preferred installers end up in `warnings`.
+fn select_installers_by_platform(
+    mut installers: Vec<PathBuf>,
+    mapper: &mut dyn FnMut(&str) -> Option<String>,
+    windows: WindowsInstaller,
+    warnings: &mut Vec<Warning>,
+) -> BTreeMap<String, PathBuf> {
+    installers.sort();
+    // With a macOS updater archive in the bundle, any DMG is only there for
+    // the download page, even when its name maps to another arch than the
+    // archive's; never let it become an updater entry.
+    if installers
+        .iter()
+        .any(|path| file_name_lossy(path).ends_with(".app.tar.gz"))
+    {
+        installers.retain(|path| {
+            let dmg = file_name_lossy(path).ends_with(".dmg");
+            if dmg {
+                log::debug!("skipping {}: the bundle has a .app.tar.gz", path.display());
+            }
+            !dmg
+        });
+    }
+    let mut selected: BTreeMap<String, (PathBuf, u8, Vec<String>)> = BTreeMap::new();
+    let mut tied = BTreeMap::<String, Vec<String>>::new();
+
+    for installer in installers {
+        let installer_name = file_name_lossy(&installer);
+        let Some(platform) = resolve_platform_key(&installer_name, mapper) else {
+            warnings.push(Warning::UnknownPlatform {
+                installer: installer_name,
+            });
+            continue;
+        };
+        let priority = installer_priority(&platform, &installer_name, windows);
+        match selected.get_mut(&platform) {
+            Some((_, existing_priority, skipped)) if *existing_priority >= priority => {
+                if *existing_priority == priority {
+                    tied.entry(platform)
+                        .or_default()
+                        .push(installer_name.clone());
+                }
+                skipped.push(installer_name);
+            }
+            Some(existing) => {
+                let previous = std::mem::replace(&mut existing.0, installer);
+                existing.1 = priority;
+                existing.2.push(file_name_lossy(&previous));
+                tied.remove(&platform);
+            }
+            None => {
+                selected.insert(platform, (installer, priority, Vec::new()));
+            }
+        }
+    }
+
+    selected
+        .into_iter()
+        .map(|(platform, (installer, _, skipped))| {
+            if let Some(tied) = tied.remove(&platform) {
+                warnings.push(Warning::DuplicatePlatform {
+                    platform: platform.clone(),
+                    selected: file_name_lossy(&installer),
+                    skipped: tied,
+                });
+            }
+            if !skipped.is_empty() {
+                log::info!(
+                    "{}: selected {}, skipped {}",
+                    platform,
+                    file_name_lossy(&installer),
+                    skipped.join(", ")
+                );
+            }
+            (platform, installer)
+        })
+        .collect()
+}
+
+fn file_name_lossy(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Prefix of the environment variables consulted for a platform whose
+/// installer has no `.sig` file, e.g. `TAURI_SIG_WINDOWS_X86_64`.
+pub const SIGNATURE_ENV_PREFIX: &str = "TAURI_SIG_";
+
+/// Returns the environment variable that may hold the signature for
+/// `platform_key`: [`SIGNATURE_ENV_PREFIX`] plus the key in upper case with
+/// `-` as `_`, so `darwin-aarch64` reads `TAURI_SIG_DARWIN_AARCH64`.
+pub fn signature_env_var(platform_key: &str) -> String {
+    format!(
+        "{}{}",
+        SIGNATURE_ENV_PREFIX,
+        platform_key.to_ascii_uppercase().replace('-', "_")
+    )
+}
+
+/// Where an installer's signature comes from.
+enum SignatureSource {
+    File(PathBuf),
+    Env(String),
+}
+
+/// Reads the signature for `platform_key` from its environment variable.
+/// The value is the `.sig` file content; a copy wrapped in another layer of
+/// base64 (as some secret stores require) is unwrapped.
+fn env_signature(platform_key: &str) -> Option<String> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let value = std::env::var(signature_env_var(platform_key)).ok()?;
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    let unwrapped = STANDARD
+        .decode(value)
+        .ok()
+        .and_then(|decoded| String::from_utf8(decoded).ok())
+        .filter(|inner| is_sig_content(inner.trim()));
+    Some(unwrapped.map_or_else(|| value.to_string(), |inner| inner.trim().to_string()))
+}
+
+/// Whether `value` is `.sig` content: base64 of minisign signature text.
+fn is_sig_content(value: &str) -> bool {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    STANDARD
+        .decode(value)
+        .is_ok_and(|text| text.starts_with(b"untrusted comment:"))
+}
+
+/// The extension of signature files unless
+/// [`GenerateOptions::signature_extension`] says otherwise.
+pub const DEFAULT_SIGNATURE_EXTENSION: &str = "sig";
+
+/// Maps each installer path to the signature next to it (`<installer>.<extension>`),
+/// in a single walk. `extension` is matched as a file-name suffix, so it may
+/// contain dots (`minisig.sig`).
+///
+/// Pairing by file name (rather than platform) keeps an MSI and an NSIS
+/// `.exe` for the same platform from overwriting each other's signature.
+fn find_signatures(
+    fs: &dyn FileSystem,
+    dir: &Path,
+    walk: WalkOptions,
+    extension: &str,
+) -> Result<HashMap<PathBuf, PathBuf>> {
+    let suffix = format!(".{}", extension);
+    let mut results = HashMap::new();
+    for sig_path in fs.walk(dir, walk)? {
+        let Some(installer_name) = sig_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(&suffix))
+            .filter(|name| !name.is_empty())
+        else {
+            continue;
+        };
+        let installer = sig_path.with_file_name(installer_name);
+        log::debug!(
+            "signature {} -> installer {}",
+            sig_path.display(),
+            installer.display()
+        );
+        results.insert(installer, sig_path);
+    }
+    Ok(results)
+}
+
+fn detect_tauri_conf_path() -> Result<PathBuf> {
+    find_tauri_conf(&std::env::current_dir()?)
+}
+
+/// Walks up from `start`, checking each directory and its `src-tauri/`,
+/// like the tauri CLI does.
+fn find_tauri_conf(start: &Path) -> Result<PathBuf> {
+    let mut searched = Vec::new();
+    for dir in start.ancestors() {
+        for conf_dir in [dir.to_path_buf(), dir.join("src-tauri")] {
+            for name in TAURI_CONF_FILE_NAMES {
+                let c = conf_dir.join(name);
+                if c.exists() {
+                    return Ok(c);
+                }
+                searched.push(c);
+            }
+        }
+    }
+    Err(LatestJsonError::ConfigNotFound { searched })
+}
+
+/// Asks the caller-supplied `mapper` first, then falls back to
+/// [`detect_platform_key`].
+fn resolve_platform_key(
+    filename: &str,
+    mapper: &mut dyn FnMut(&str) -> Option<String>,
+) -> Option<String> {
+    // Mappers written against the old string API may still answer "unknown".
+    mapper(filename)
+        .filter(|key| key != "unknown")
+        .or_else(|| detect_platform_key(filename).map(|target| target.key()))
+}
+
+/// The `X.Y.Z` version Tauri puts in artifact names such as
+/// `App_1.2.0_x64_en-US.msi` or `app-1.2.0-1.x86_64.rpm`, if any. Only the
+/// first `_`/`-`-delimited token starting with `X.Y.Z` counts.
+pub(crate) fn version_in_file_name(file_name: &str) -> Option<semver::Version> {
+    file_name.split(['_', '-']).find_map(|token| {
+        let mut parts = token.splitn(4, '.');
+        let mut number = || parts.next()?.parse::<u64>().ok();
+        Some(semver::Version::new(number()?, number()?, number()?))
+    })
+}
+
+/// Guards against publishing artifacts from another build.
+///
+/// Installers whose file name version (`X.Y.Z`) differs from `version` are
+/// dropped with a [`Warning`] when `filter` is set, and reported as
+/// [`LatestJsonError::MixedVersions`] otherwise. Installers without a
+/// version in their name are always kept, and nothing is checked when
+/// `version` is not semver (see `GenerateOptions::strict_semver`).
+fn check_installer_versions(
+    installers: Vec<PathBuf>,
+    version: &str,
+    filter: bool,
+    warnings: &mut Vec<Warning>,
+) -> Result<Vec<PathBuf>> {
+    let Ok(current) = semver::Version::parse(version) else {
+        log::debug!(
+            "{} is not semver, not comparing installer file name versions",
+            version
+        );
+        return Ok(installers);
+    };
+    let current = semver::Version::new(current.major, current.minor, current.patch);
+    let (kept, mismatched): (Vec<_>, Vec<_>) = installers.into_iter().partition(|path| {
+        version_in_file_name(&file_name_lossy(path)).is_none_or(|found| found == current)
+    });
+    if mismatched.is_empty() {
+        return Ok(kept);
+    }
+    if !filter {
+        return Err(LatestJsonError::MixedVersions {
+            version: version.to_string(),
+            mismatched,
+        });
+    }
+    warnings.extend(mismatched.iter().map(|path| Warning::VersionMismatch {
+        installer: file_name_lossy(path),
+        version: version.to_string(),
+    }));
+    Ok(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs::{create_dir_all, File};
+    use std::io::Write;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Serializes tests that touch process-wide state (cwd, environment).
+    static CWD_LOCK: Mutex<()> = Mutex::new(());
+    static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    /// A well-formed `.pub` file (base64-wrapped, as Tauri stores it).
+    pub(crate) const TEST_PUBKEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDA4MDcwNjA1MDQwMzAyMDEKUldRQkFnTUVCUVlIQ0FBQkFnTUVCUVlIQ0FrS0N3d05EZzhRRVJJVEZCVVdGeGdaR2hzY0hSNGYK";
+
+    struct CurrentDirGuard {
+        original: PathBuf,
+    }
+
+    impl CurrentDirGuard {
+        fn switch_to(path: &Path) -> Self {
+            let original = std::env::current_dir().unwrap();
+            std::env::set_current_dir(path).unwrap();
+            Self { original }
+        }
+    }
+
+    impl Drop for CurrentDirGuard {
+        fn drop(&mut self) {
+            std::env::set_current_dir(&self.original).unwrap();
+        }
+    }
+
+    /// Unsets an environment variable (e.g. `CARGO_TARGET_DIR` in CI) and
+    /// restores it on drop. Hold [`CWD_LOCK`] while it is alive.
+    struct EnvVarGuard {
+        name: &'static str,
+        original: Option<std::ffi::OsString>,
+    }
+
+    impl EnvVarGuard {
+        fn remove(name: &'static str) -> Self {
+            let original = std::env::var_os(name);
+            std::env::remove_var(name);
+            Self { name, original }
+        }
+    }
+
+    impl Drop for EnvVarGuard {
+        fn drop(&mut self) {
+            if let Some(value) = &self.original {
+                std::env::set_var(self.name, value);
+            }
+        }
+    }
+
+    pub(crate) fn make_temp_dir() -> PathBuf {
+        let mut base = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let seq = TEST_COUNTER.fetch_add(1, Ordering::Relaxed);
+        base.push(format!(
+            "tauri-latest-json-test-{}-{}-{}",
+            std::process::id(),
+            nanos,
+            seq
+        ));
+        create_dir_all(&base).unwrap();
+        base
+    }
+
+    pub(crate) fn write_file(path: &Path, content: &str) {
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).unwrap();
+        }
+        std::fs::write(path, content).unwrap();
+    }
+
+    /// Builds with default options and no mapper, then writes `output_path`.
+    fn generate_for_project(
+        bundle_dir: &Path,
+        public_key: &str,
+        download_url_base: &str,
+        notes: &str,
+        project_dir: &Path,
+        output_path: &Path,
+    ) -> Result<()> {
+        let latest_json = build_latest_json_for_project(
+            bundle_dir,
+            public_key,
+            project_dir,
+            &GenerateOptions::new(download_url_base).notes(notes),
+            &mut |_| None,
+        )?;
+        write_latest_json(&RealFs, &latest_json, output_path, true)
+    }
+
+    fn key(filename: &str) -> String {
+        detect_platform_key(filename).expect(filename).key()
+    }
+
+    #[test]
+    fn test_detect_platform_key_variants() {
+        assert_eq!(key("app_0.1.0_x64_en-US.msi"), "windows-x86_64");
+        assert_eq!(key("app_0.1.0_x64_en-US.exe"), "windows-x86_64");
+        assert_eq!(key("MyApp_1.0.0_arm64_en-US.msi"), "windows-aarch64");
+        assert_eq!(key("MyApp_1.0.0_arm64-setup.exe"), "windows-aarch64");
+        assert_eq!(key("arm64_1.0.0_x64_en-US.msi"), "windows-x86_64");
+        assert_eq!(key("MyArm64Tool_1.0.0_x64-setup.exe"), "windows-x86_64");
+        assert_eq!(key("app_0.1.0_x64.dmg"), "darwin-x86_64");
+        assert_eq!(key("app_0.1.0_arm64.dmg"), "darwin-aarch64");
+        assert_eq!(key("app_0.1.0_aarch64.app.tar.gz"), "darwin-aarch64");
+        assert_eq!(key("app_0.1.0_x64.app.tar.gz"), "darwin-x86_64");
+        assert_eq!(key("app_0.1.0_universal.app.tar.gz"), "darwin-universal");
+        assert_eq!(key("app_0.1.0_universal.dmg"), "darwin-universal");
+        assert_eq!(key("AppImage-0.1.0-x86_64.AppImage"), "linux-x86_64");
+        assert_eq!(key("AppImage-0.1.0-arm64.AppImage"), "linux-aarch64");
+        assert_eq!(key("app_0.1.0_amd64.deb"), "linux-x86_64");
+        assert_eq!(key("app_0.1.0_aarch64.rpm"), "linux-aarch64");
+        assert_eq!(key("app_0.1.0_aarch64.AppImage"), "linux-aarch64");
+        assert_eq!(key("app_0.1.0_armhf.AppImage"), "linux-armv7");
+        assert_eq!(key("app_0.1.0_armhf.deb"), "linux-armv7");
+        assert_eq!(key("app-0.1.0-1.armv7hl.rpm"), "linux-armv7");
+        assert_eq!(key("app-0.1.0-x64.tar.gz"), "linux-x86_64");
+        assert_eq!(detect_platform_key("unknown.bin"), None);
+    }
+
+    #[test]
+    fn test_detect_platform_key_maps_32_bit_x86_without_catching_x86_64() {
+        assert_eq!(key("MyApp_1.0.0_x86_en-US.msi"), "windows-i686");
+        assert_eq!(key("MyApp_1.0.0_x86-setup.exe"), "windows-i686");
+        assert_eq!(key("MyApp_1.0.0_ia32-setup.exe"), "windows-i686");
+        assert_eq!(key("my-app_1.0.0_i386.deb"), "linux-i686");
+        assert_eq!(key("my-app-1.0.0-1.i686.rpm"), "linux-i686");
+        assert_eq!(key("my-app_1.0.0_i386.AppImage"), "linux-i686");
+        assert_eq!(key("MyApp_1.0.0_x86_64-setup.exe"), "windows-x86_64");
+        assert_eq!(key("my-app_1.0.0_x86_64.AppImage"), "linux-x86_64");
+        assert_eq!(key("x86_1.0.0_x64_en-US.msi"), "windows-x86_64");
+    }
+
+    #[test]
+    fn test_detect_platform_key_uses_target_triple_for_mac_archives() {
+        assert_eq!(
+            key("app_0.1.0_aarch64-apple-darwin.app.tar.gz"),
+            "darwin-aarch64"
+        );
+        assert_eq!(
+            key("app_0.1.0_x86_64-apple-darwin.app.tar.gz"),
+            "darwin-x86_64"
+        );
+    }
+
+    #[test]
+    fn test_detect_platform_key_prefers_target_triple_over_extension() {
+        assert_eq!(
+            key("app_1.0.0_aarch64-pc-windows-msvc.exe"),
+            "windows-aarch64"
+        );
+        assert_eq!(
+            key("app_1.0.0_x86_64-unknown-linux-gnu.tar.gz"),
+            "linux-x86_64"
+        );
+        assert_eq!(
+            key("app_1.0.0_armv7-unknown-linux-gnueabihf.deb"),
+            "linux-armv7"
+        );
+        assert_eq!(
+            key("App_1.0.0_Universal-Apple-Darwin.app.tar.gz"),
+            "darwin-universal"
+        );
+        assert_eq!(key("app_1.0.0_i686-pc-windows-msvc.msi"), "windows-i686");
+    }
+
+    #[test]
+    fn test_find_installers_keeps_dmg_but_selects_mac_archive_for_updater() {
+        let dir = make_temp_dir();
+        write_file(&dir.join("dmg").join("app_0.1.0_aarch64.dmg"), "dmg");
+        write_file(
+            &dir.join("macos").join("app_0.1.0_aarch64.app.tar.gz"),
+            "archive",
+        );
+
+        let all = find_installers(&dir).unwrap();
+        assert_eq!(all.len(), 2);
+
+        let selected = select_installers_by_platform(
+            all,
+            &mut |_| None,
+            WindowsInstaller::Msi,
+            &mut Vec::new(),
+        );
+        assert_eq!(selected.len(), 1);
+        assert!(selected["darwin-aarch64"]
+            .to_string_lossy()
+            .ends_with("app_0.1.0_aarch64.app.tar.gz"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_find_installers_with_extensions_uses_allowlist() {
+        let dir = make_temp_dir();
+        write_file(&dir.join("app_1.0.0_amd64.deb"), "deb");
+        write_file(&dir.join("app_1.0.0_amd64.AppImage"), "appimage");
+        write_file(&dir.join("app_1.0.0_x64.ZIP"), "zip");
+
+        let mut found = find_installers_with_extensions(&dir, &["appimage", ".zip"]).unwrap();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                dir.join("app_1.0.0_amd64.AppImage"),
+                dir.join("app_1.0.0_x64.ZIP"),
+            ]
+        );
+        assert_eq!(find_installers(&dir).unwrap().len(), 2);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_version_in_file_name_reads_tauri_artifact_names() {
+        let version = |name| version_in_file_name(name).map(|v| v.to_string());
+        assert_eq!(version("App_1.2.0_x64_en-US.msi").as_deref(), Some("1.2.0"));
+        assert_eq!(version("App_1.1.0.msi").as_deref(), Some("1.1.0"));
+        assert_eq!(version("app-1.2.0-1.x86_64.rpm").as_deref(), Some("1.2.0"));
+        assert_eq!(
+            version("App_1.3.0-beta.1_x64-setup.exe").as_deref(),
+            Some("1.3.0")
+        );
+        assert_eq!(version("App.app.tar.gz"), None);
+        assert_eq!(version("app_x86_64.AppImage"), None);
+    }
+
+    #[test]
+    fn test_check_installer_versions_filters_or_reports_stale_builds() {
+        let installers = vec![
+            PathBuf::from("/bundle/App_1.0.0_x64_en-US.msi"),
+            PathBuf::from("/bundle/App_1.1.0_x64_en-US.msi"),
+            PathBuf::from("/bundle/App.app.tar.gz"),
+        ];
+
+        assert_eq!(
+            check_installer_versions(installers.clone(), "1.1.0", true, &mut Vec::new()).unwrap(),
+            [
+                PathBuf::from("/bundle/App_1.1.0_x64_en-US.msi"),
+                PathBuf::from("/bundle/App.app.tar.gz"),
+            ]
+        );
+        match check_installer_versions(installers.clone(), "1.1.0", false, &mut Vec::new()) {
+            Err(LatestJsonError::MixedVersions { mismatched, .. }) => {
+                assert_eq!(
+                    mismatched,
+                    [PathBuf::from("/bundle/App_1.0.0_x64_en-US.msi")]
+                )
+            }
+            other => panic!("expected MixedVersions, got {:?}", other),
+        }
+        // Only a stale build in the bundle: still checked against the version.
+        let mut warnings = Vec::new();
+        let stale = vec![PathBuf::from("/bundle/App_1.0.0_x64_en-US.msi")];
+        assert!(matches!(
+            check_installer_versions(stale.clone(), "1.1.0", false, &mut Vec::new()),
+            Err(LatestJsonError::MixedVersions { mismatched, .. }) if mismatched == stale
+        ));
+        assert!(
+            check_installer_versions(stale.clone(), "1.1.0", true, &mut warnings)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            warnings,
+            [Warning::VersionMismatch {
+                installer: "App_1.0.0_x64_en-US.msi".to_string(),
+                version: "1.1.0".to_string(),
+            }]
+        );
+        // A non-semver version (strict checking off) cannot be compared.
+        assert_eq!(
+            check_installer_versions(installers.clone(), "nightly", false, &mut Vec::new())
+                .unwrap(),
+            installers
+        );
+    }
+
+    #[test]
+    fn test_find_installers_skips_hidden_and_deep_directories() {
+        let dir = make_temp_dir();
+        write_file(&dir.join("msi/app_1.0.0_x64_en-US.msi"), "msi");
+        write_file(&dir.join(".cache/helper.exe"), "exe");
+        write_file(&dir.join("a/b/c/d/stray.exe"), "exe");
+
+        assert_eq!(
+            find_installers(&dir).unwrap(),
+            vec![dir.join("msi/app_1.0.0_x64_en-US.msi")]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_are_skipped_or_followed_without_duplicates() {
+        let dir = make_temp_dir();
+        write_file(&dir.join("cache/app_1.0.0_amd64.deb"), "deb");
+        write_file(&dir.join("bundle/deb/app_1.0.0_amd64.deb"), "deb");
+        std::os::unix::fs::symlink(dir.join("cache"), dir.join("bundle/cache")).unwrap();
+        std::os::unix::fs::symlink(dir.join("bundle/deb"), dir.join("bundle/deb-link")).unwrap();
+        let walk = |follow_symlinks| {
+            let options = WalkOptions {
+                follow_symlinks,
+                ..WalkOptions::default()
+            };
+            RealFs.walk(&dir.join("bundle"), options).unwrap()
+        };
+
+        assert_eq!(walk(false), [dir.join("bundle/deb/app_1.0.0_amd64.deb")]);
+        assert_eq!(
+            walk(true),
+            [
+                dir.join("bundle/cache/app_1.0.0_amd64.deb"),
+                dir.join("bundle/deb/app_1.0.0_amd64.deb"),
+            ]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_mock_filesystem_walk_honors_max_depth_and_hidden_directories() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app.deb", "deb")
+            .with_file("/bundle/msi/app.msi", "msi")
+            .with_file("/bundle/.git/app.exe", "exe")
+            .with_file("/bundle/.app.AppImage", "appimage");
+        let walk = |max_depth| {
+            let options = WalkOptions {
+                max_depth,
+                ..WalkOptions::default()
+            };
+            mock.walk(Path::new("/bundle"), options).unwrap()
+        };
+
+        assert_eq!(
+            walk(4),
+            [
+                PathBuf::from("/bundle/.app.AppImage"),
+                PathBuf::from("/bundle/app.deb"),
+                PathBuf::from("/bundle/msi/app.msi"),
+            ]
+        );
+        assert_eq!(
+            walk(1),
+            [
+                PathBuf::from("/bundle/.app.AppImage"),
+                PathBuf::from("/bundle/app.deb"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inspect_bundle_reports_platform_and_signature() {
+        let dir = make_temp_dir();
+        write_file(&dir.join("msi/app_1.0.0_x64_en-US.msi"), "msi");
+        write_file(&dir.join("msi/app_1.0.0_x64_en-US.msi.sig"), "sig");
+        write_file(&dir.join("dmg/app_1.0.0_aarch64.dmg"), "dmg");
+
+        assert_eq!(
+            inspect_bundle(&dir).unwrap(),
+            vec![
+                InstallerInfo {
+                    path: dir.join("dmg/app_1.0.0_aarch64.dmg"),
+                    platform_key: "darwin-aarch64".to_string(),
+                    signature_path: None,
+                },
+                InstallerInfo {
+                    path: dir.join("msi/app_1.0.0_x64_en-US.msi"),
+                    platform_key: "windows-x86_64".to_string(),
+                    signature_path: Some(dir.join("msi/app_1.0.0_x64_en-US.msi.sig")),
+                },
+            ]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_windows_installer_preference_picks_msi_or_nsis() {
+        let installers = vec![
+            PathBuf::from("bundle/nsis/app_1.0.0_x64-setup.exe"),
+            PathBuf::from("bundle/msi/app_1.0.0_x64_en-US.msi"),
+        ];
+
+        let msi = select_installers_by_platform(
+            installers.clone(),
+            &mut |_| None,
+            WindowsInstaller::Msi,
+            &mut Vec::new(),
+        );
+        assert_eq!(
+            msi["windows-x86_64"],
+            PathBuf::from("bundle/msi/app_1.0.0_x64_en-US.msi")
+        );
+        let nsis = select_installers_by_platform(
+            installers,
+            &mut |_| None,
+            WindowsInstaller::Nsis,
+            &mut Vec::new(),
+        );
+        assert_eq!(
+            nsis["windows-x86_64"],
+            PathBuf::from("bundle/nsis/app_1.0.0_x64-setup.exe")
+        );
+    }
+
+    #[test]
+    fn test_read_version_prefers_package_json() {
+        let dir = make_temp_dir();
+        {
+            let mut f = File::create(dir.join("Cargo.toml")).unwrap();
+            writeln!(
+                f,
+                "[package]\nname = \"dummy\"\nversion = \"0.2.0\"\n\n[dependencies]\n"
+            )
+            .unwrap();
+        }
+        {
+            let mut f = File::create(dir.join("package.json")).unwrap();
+            writeln!(f, "{{\"name\":\"dummy\",\"version\":\"1.2.3\"}}").unwrap();
+        }
+        let v = read_version(&dir, VersionSource::Auto).unwrap();
+        assert_eq!(v, "1.2.3");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_read_version_can_prefer_cargo_toml() {
+        let dir = make_temp_dir();
+        write_file(
+            &dir.join("Cargo.toml"),
+            "[package]\nname = \"dummy\"\nversion = \"0.2.0\"\n",
+        );
+        write_file(
+            &dir.join("package.json"),
+            r#"{"name":"dummy","version":"1.2.3"}"#,
+        );
+
+        assert_eq!(
+            read_version(&dir, VersionSource::CargoToml).unwrap(),
+            "0.2.0"
+        );
+        assert_eq!(
+            read_version(&dir, VersionSource::PackageJson).unwrap(),
+            "1.2.3"
+        );
+        fs::remove_file(dir.join("Cargo.toml")).unwrap();
+        assert_eq!(
+            read_version(&dir, VersionSource::CargoToml).unwrap(),
+            "1.2.3"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_version_can_prefer_tauri_conf_and_follow_package_json_link() {
+        let dir = make_temp_dir();
+        write_file(
+            &dir.join("package.json"),
+            r#"{"name":"dummy","version":"1.2.3"}"#,
+        );
+        write_file(
+            &dir.join("src-tauri").join("tauri.conf.json"),
+            r#"{"version":"2.0.0"}"#,
+        );
+
+        assert_eq!(
+            read_version(&dir, VersionSource::TauriConf).unwrap(),
+            "2.0.0"
+        );
+        assert_eq!(read_version(&dir, VersionSource::Auto).unwrap(), "1.2.3");
+
+        write_file(
+            &dir.join("src-tauri").join("tauri.conf.json"),
+            r#"{"version":"../package.json"}"#,
+        );
+        assert_eq!(
+            read_version(&dir, VersionSource::TauriConf).unwrap(),
+            "1.2.3"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_version_from_cargo_toml() {
+        let dir = make_temp_dir();
+        {
+            let mut f = File::create(dir.join("Cargo.toml")).unwrap();
+            writeln!(
+                f,
+                "[package]\nname = \"dummy\"\nversion = \"9.9.9\"\n\n[dependencies]\n"
+            )
+            .unwrap();
+        }
+        let v = read_version(&dir, VersionSource::Auto).unwrap();
+        assert_eq!(v, "9.9.9");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_read_version_from_src_tauri_cargo_toml() {
+        let dir = make_temp_dir();
+        create_dir_all(dir.join("src-tauri")).unwrap();
+        {
+            let mut f = File::create(dir.join("src-tauri").join("Cargo.toml")).unwrap();
+            writeln!(
+                f,
+                "[package]\nname = \"dummy\"\nversion = \"3.4.5\"\n\n[dependencies]\n"
+            )
+            .unwrap();
+        }
+        let v = read_version(&dir, VersionSource::Auto).unwrap();
+        assert_eq!(v, "3.4.5");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_resolve_public_key_prefers_env_over_config() {
+        let _cwd_guard = CWD_LOCK.lock().unwrap_or_else(|poison| poison.into_inner());
+        let dir = make_temp_dir();
+        let conf_path = dir.join("tauri.conf.json");
+        write_file(&conf_path, r#"{"plugins":{"updater":{"pubkey":""}}}"#);
+
+        std::env::remove_var(PUBLIC_KEY_ENV);
+        assert!(matches!(
+            resolve_public_key(None, Some(&conf_path)),
+            Err(LatestJsonError::PublicKeyMissing)
+        ));
+
+        std::env::set_var(PUBLIC_KEY_ENV, "env-pubkey\n");
+        assert_eq!(
+            resolve_public_key(None, Some(&conf_path)).unwrap(),
+            ["env-pubkey"]
+        );
+        // The config is not needed at all when the env var is set.
+        assert_eq!(
+            resolve_public_key(None, Some(&dir.join("missing.json"))).unwrap(),
+            ["env-pubkey"]
+        );
+
+        write_file(
+            &conf_path,
+            r#"{"plugins":{"updater":{"pubkey":"conf-pubkey"}}}"#,
+        );
+        std::env::set_var(PUBLIC_KEY_ENV, "");
+        assert_eq!(
+            resolve_public_key(None, Some(&conf_path)).unwrap(),
+            ["conf-pubkey"]
+        );
+        std::env::remove_var(PUBLIC_KEY_ENV);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_public_key_file_precedence() {
+        let _cwd_guard = CWD_LOCK.lock().unwrap_or_else(|poison| poison.into_inner());
+        let dir = make_temp_dir();
+        let conf_path = dir.join("tauri.conf.json");
+        write_file(&dir.join("keys").join("app.key.pub"), "file-pubkey\n");
+        write_file(&dir.join("explicit.pub"), "explicit-pubkey");
+        write_file(
+            &conf_path,
+            r#"{"plugins":{"updater":{"pubkey":"keys/app.key.pub"}}}"#,
+        );
+
+        std::env::remove_var(PUBLIC_KEY_ENV);
+        assert_eq!(
+            resolve_public_key(None, Some(&conf_path)).unwrap(),
+            ["file-pubkey"]
+        );
+
+        std::env::set_var(PUBLIC_KEY_ENV, "env-pubkey");
+        assert_eq!(
+            resolve_public_key(None, Some(&conf_path)).unwrap(),
+            ["env-pubkey"]
+        );
+        assert_eq!(
+            resolve_public_key(Some(&dir.join("explicit.pub")), Some(&conf_path)).unwrap(),
+            ["explicit-pubkey"]
+        );
+        std::env::remove_var(PUBLIC_KEY_ENV);
+
+        assert!(matches!(
+            resolve_public_key(Some(&dir.join("missing.pub")), None),
+            Err(LatestJsonError::PublicKeyFile { .. })
+        ));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_validate_public_key_accepts_pub_file_and_bare_key() {
+        validate_public_key(TEST_PUBKEY).unwrap();
+        validate_public_key("RWQBAgMEBQYHCAABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhscHR4f").unwrap();
+
+        for bad in [
+            "test-pubkey",
+            &TEST_PUBKEY[..TEST_PUBKEY.len() - 12],
+            "RWQBAgMEBQYHCAABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhsc",
+            "dW50cnVzdGVkIGNvbW1lbnQ6IGtleQ==",
+        ] {
+            assert!(
+                matches!(
+                    validate_public_key(bad),
+                    Err(LatestJsonError::InvalidPublicKey(_))
+                ),
+                "accepted {bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_public_key_follows_config_schema() {
+        let dir = make_temp_dir();
+        let conf_path = dir.join("tauri.conf.json");
+        // Tauri 1 configs may carry an unrelated top-level `plugins` object.
+        write_file(
+            &conf_path,
+            r#"{"plugins":{"updater":{"pubkey":"plugin-config"}},"tauri":{"updater":{"pubkey":"v1-pubkey"}}}"#,
+        );
+        assert_eq!(read_public_key(&conf_path).unwrap(), ["v1-pubkey"]);
+
+        write_file(
+            &conf_path,
+            r#"{"app":{},"plugins":{"updater":{"pubkey":"v2-pubkey"}}}"#,
+        );
+        assert_eq!(read_public_key(&conf_path).unwrap(), ["v2-pubkey"]);
+
+        // Rotation keys follow `pubkey`, without duplicates.
+        write_file(
+            &conf_path,
+            r#"{"plugins":{"updater":{"pubkey":"new","pubkeys":["old","new",""]}}}"#,
+        );
+        assert_eq!(read_public_key(&conf_path).unwrap(), ["new", "old"]);
+
+        write_file(&conf_path, r#"{"tauri":{"bundle":{}}}"#);
+        let err = read_public_key(&conf_path).unwrap_err();
+        assert!(matches!(err, LatestJsonError::UpdaterNotConfigured));
+        let message = err.to_string();
+        assert!(message.contains("plugins.updater.pubkey"));
+        assert!(message.contains("tauri.updater.pubkey"));
+
+        write_file(
+            &conf_path,
+            r#"{"plugins":{"updater":{"endpoints":["https://cdn/latest.json"]}}}"#,
+        );
+        assert!(matches!(
+            read_public_key(&conf_path),
+            Err(LatestJsonError::PublicKeyMissing)
+        ));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_read_public_key_accepts_json5_and_commented_json() {
+        let dir = make_temp_dir();
+        let json5_path = dir.join("tauri.conf.json5");
+        write_file(
+            &json5_path,
+            "{\n  // updater settings\n  plugins: { updater: { pubkey: 'json5-pubkey', }, },\n}",
+        );
+        assert_eq!(read_public_key(&json5_path).unwrap(), ["json5-pubkey"]);
+
+        let json_path = dir.join("tauri.conf.json");
+        write_file(
+            &json_path,
+            "{\n  // updater settings\n  \"plugins\": {\"updater\": {\"pubkey\": \"commented\"}}\n}",
+        );
+        assert_eq!(read_public_key(&json_path).unwrap(), ["commented"]);
+
+        write_file(&json_path, "{ not json");
+        let err = read_public_key(&json_path).unwrap_err();
+        assert!(matches!(
+            &err,
+            LatestJsonError::File { path, source }
+                if *path == json_path && matches!(**source, LatestJsonError::Json(_))
+        ));
+        assert!(err
+            .to_string()
+            .starts_with(&json_path.display().to_string()));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_package_json_lookup_walks_up_to_workspace_root() {
+        let dir = make_temp_dir();
+        let app = dir.join("apps").join("desktop");
+        write_file(&dir.join("pnpm-workspace.yaml"), "packages:\n  - apps/*\n");
+        write_file(&dir.join("package.json"), r#"{"version":"2.0.0"}"#);
+        write_file(
+            &dir.join("apps").join("package.json"),
+            r#"{"version":"1.5.0"}"#,
+        );
+        write_file(&app.join("package.json"), r#"{"name":"desktop"}"#);
+        write_file(
+            &app.join("src-tauri").join("Cargo.toml"),
+            "[package]\nname = \"desktop\"\nversion = \"0.1.0\"\n",
+        );
+
+        let read = |lookup| read_version_with_file(&app, VersionSource::Auto, lookup).unwrap();
+        assert_eq!(
+            read(PackageJsonLookup::Local),
+            (
+                "0.1.0".to_string(),
+                app.join("src-tauri").join("Cargo.toml")
+            )
+        );
+        assert_eq!(
+            read(PackageJsonLookup::Nearest),
+            ("1.5.0".to_string(), dir.join("apps").join("package.json"))
+        );
+        assert_eq!(
+            read(PackageJsonLookup::WorkspaceRoot),
+            ("2.0.0".to_string(), dir.join("package.json"))
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_read_version_from_src_tauri_json5_config() {
+        let dir = make_temp_dir();
+        write_file(
+            &dir.join("src-tauri").join("tauri.conf.json5"),
+            "{ version: '4.5.6', // release\n}",
+        );
+        assert_eq!(read_version(&dir, VersionSource::Auto).unwrap(), "4.5.6");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_bundle_dir_candidates_honor_cargo_target_dir() {
+        let dir = make_temp_dir();
+        let candidates =
+            bundle_dir_candidates(&dir, Some(Path::new("/tmp/build")), &["release", "debug"]);
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("/tmp/build/release/bundle"),
+                PathBuf::from("/tmp/build/debug/bundle"),
+            ]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_detect_bundle_dir_honors_env_override() {
+        let _cwd_guard = CWD_LOCK.lock().unwrap_or_else(|poison| poison.into_inner());
+        let dir = make_temp_dir();
+        let artifacts = dir.join("artifacts").join("bundle");
+        create_dir_all(&artifacts).unwrap();
+
+        std::env::set_var(BUNDLE_DIR_ENV, &artifacts);
+        assert_eq!(detect_bundle_dir(None).unwrap(), artifacts);
+
+        let missing = dir.join("missing");
+        std::env::set_var(BUNDLE_DIR_ENV, &missing);
+        match detect_bundle_dir(None) {
+            Err(LatestJsonError::BundleDirNotFound { searched, .. }) => {
+                assert_eq!(searched, vec![missing])
+            }
+            other => panic!("expected BundleDirNotFound, got {:?}", other),
+        }
+        std::env::remove_var(BUNDLE_DIR_ENV);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_detect_bundle_dir_searches_only_the_given_profile() {
+        let _cwd_guard = CWD_LOCK.lock().unwrap_or_else(|poison| poison.into_inner());
+        let _target_dir_guard = EnvVarGuard::remove("CARGO_TARGET_DIR");
+        let dir = make_temp_dir();
+        let debug_bundle = dir.join("target").join("debug").join("bundle");
+        create_dir_all(&debug_bundle).unwrap();
+        create_dir_all(dir.join("target").join("release").join("bundle")).unwrap();
+        let original = std::env::current_dir().unwrap();
+        std::env::set_current_dir(&dir).unwrap();
+
+        let found = detect_bundle_dir(Some("debug"));
+        let missing = detect_bundle_dir(Some("ci"));
+        std::env::set_current_dir(original).unwrap();
+
+        assert!(found.unwrap().ends_with("target/debug/bundle"));
+        let err = missing.unwrap_err();
+        assert!(err.to_string().contains("target/ci/bundle"));
+        match err {
+            LatestJsonError::BundleDirNotFound { searched, profile } => {
+                assert_eq!(profile, "ci");
+                assert!(searched.iter().all(|path| path.ends_with("ci/bundle")));
+            }
+            other => panic!("expected BundleDirNotFound, got {:?}", other),
+        }
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_bundle_dir_candidates_include_workspace_target() {
+        let dir = make_temp_dir();
+        write_file(
+            &dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"apps/desktop\"]\n",
+        );
+        let crate_dir = dir.join("apps").join("desktop");
+        create_dir_all(&crate_dir).unwrap();
+
+        let candidates = bundle_dir_candidates(&crate_dir, None, &["release", "debug"]);
+        assert_eq!(candidates[0], crate_dir.join("target/release/bundle"));
+        assert!(candidates.contains(&dir.join("target").join("release").join("bundle")));
+        assert!(candidates.contains(&dir.join("target").join("debug").join("bundle")));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_read_version_ignores_dependency_versions() {
+        let dir = make_temp_dir();
+        write_file(
+            &dir.join("Cargo.toml"),
+            "[dependencies]\nserde = { version = \"1.0\" }\n\n[package]\nname = \"dummy\"\nversion = \"2.3.4\"\n",
+        );
+        assert_eq!(read_version(&dir, VersionSource::Auto).unwrap(), "2.3.4");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_read_version_resolves_workspace_inherited_version() {
+        let dir = make_temp_dir();
+        write_file(
+            &dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"src-tauri\"]\n\n[workspace.package]\nversion = \"5.6.7\"\n",
+        );
+        write_file(
+            &dir.join("src-tauri").join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion.workspace = true\n",
+        );
+        assert_eq!(read_version(&dir, VersionSource::Auto).unwrap(), "5.6.7");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_build_latest_json_adds_platform_notes_with_global_fallback() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("bundle");
+        write_file(&dir.join("package.json"), r#"{"version":"1.0.0"}"#);
+        write_file(&bundle_dir.join("app_1.0.0_x64_en-US.msi"), "msi");
+        write_file(&bundle_dir.join("app_1.0.0_x64_en-US.msi.sig"), "sig-win");
+        write_file(&bundle_dir.join("app_aarch64.app.tar.gz"), "tar");
+        write_file(&bundle_dir.join("app_aarch64.app.tar.gz.sig"), "sig-mac");
+
+        let options = GenerateOptions::new("https://example.com")
+            .notes("Bug fixes")
+            .platform_notes(HashMap::from([(
+                "darwin-aarch64".to_string(),
+                "Re-grant accessibility permissions".to_string(),
+            )]));
+        let latest =
+            build_latest_json_for_project(&bundle_dir, "pubkey", &dir, &options, &mut |_| None)
+                .unwrap();
+
+        assert_eq!(latest.notes, "Bug fixes");
+        assert_eq!(
+            latest.platforms["darwin-aarch64"].notes.as_deref(),
+            Some("Re-grant accessibility permissions")
+        );
+        assert_eq!(
+            latest.platforms["windows-x86_64"].notes.as_deref(),
+            Some("Bug fixes")
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(all(feature = "chrono", not(feature = "verify-signature")))]
+    #[test]
+    fn test_build_latest_json_uses_injected_clock_for_pub_date() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("bundle");
+        write_file(&dir.join("package.json"), r#"{"version":"1.0.0"}"#);
+        write_file(&bundle_dir.join("app_1.0.0_amd64.AppImage"), "appimage");
+        write_file(&bundle_dir.join("app_1.0.0_amd64.AppImage.sig"), "sig");
+
+        let pub_date = chrono::DateTime::parse_from_rfc3339("2024-05-01T12:30:00Z")
+            .unwrap()
+            .to_utc();
+        let latest = build_latest_json_for_project(
+            &bundle_dir,
+            "pubkey",
+            &dir,
+            &GenerateOptions::new("https://example.com")
+                .notes("notes")
+                .pub_date(pub_date),
+            &mut |_| None,
+        )
+        .unwrap();
+
+        assert_eq!(latest.pub_date, "2024-05-01T12:30:00Z");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_sha256_file_matches_known_digest() {
+        let dir = make_temp_dir();
+        let path = dir.join("app.AppImage");
+        write_file(&path, "abc");
+
+        assert_eq!(
+            sha256_file(&RealFs, &path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_download_url_requires_absolute_http_url() {
+        assert!(validate_download_url("https://cdn.example.com/downloads", false).is_ok());
+        assert!(
+            validate_download_url("https://{{target}}.cdn.example.com/{{version}}", false).is_ok()
+        );
+        for url in [
+            "cdn.example.com/downloads",
+            "ftp://cdn.example.com",
+            "file:///tmp/dist",
+        ] {
+            assert!(matches!(
+                validate_download_url(url, false),
+                Err(LatestJsonError::InvalidDownloadUrl { .. })
+            ));
+        }
+        assert!(validate_download_url("file:///tmp/dist", true).is_ok());
+    }
+
+    #[test]
+    fn test_installer_url_substitutes_placeholders() {
+        assert_eq!(
+            installer_url(
+                "https://cdn/{{version}}/{{target}}/{{file}}",
+                "1.2.0",
+                "darwin-aarch64",
+                "app.app.tar.gz"
+            ),
+            "https://cdn/1.2.0/darwin-aarch64/app.app.tar.gz"
+        );
+        assert_eq!(
+            installer_url(
+                "https://cdn/{{arch}}",
+                "1.2.0",
+                "linux-x86_64",
+                "app.AppImage"
+            ),
+            "https://cdn/x86_64"
+        );
+        assert_eq!(
+            installer_url(
+                "https://cdn/v1.2.0",
+                "1.2.0",
+                "linux-x86_64",
+                "app.AppImage"
+            ),
+            "https://cdn/v1.2.0/app.AppImage"
+        );
+        assert_eq!(
+            installer_url(
+                "https://cdn/My Builds",
+                "1.2.0",
+                "windows-x86_64",
+                "My App+Pro_1.2.0_x64.msi"
+            ),
+            "https://cdn/My Builds/My%20App%2BPro_1.2.0_x64.msi"
+        );
+        assert_eq!(
+            installer_url("https://cdn/downloads/", "1.2.0", "linux-x86_64", "app.deb"),
+            "https://cdn/downloads/app.deb"
+        );
+    }
+
+    #[test]
+    fn test_url_path_converts_windows_separators() {
+        assert_eq!(
+            url_path("nsis\\App_1.0.0_x64-setup.exe", '\\'),
+            "nsis/App_1.0.0_x64-setup.exe"
+        );
+        assert_eq!(url_path("nsis/App.exe", '/'), "nsis/App.exe");
+        assert_eq!(
+            installer_url(
+                "https://cdn",
+                "1.0.0",
+                "windows-x86_64",
+                &url_path("a b\\c.exe", '\\')
+            ),
+            "https://cdn/a%20b/c.exe"
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_relative_url_path_uses_forward_slashes_on_windows() {
+        assert_eq!(
+            relative_url_path(
+                Path::new(r"C:\bundle"),
+                Path::new(r"C:\bundle\nsis\App.exe")
+            )
+            .as_deref(),
+            Some("nsis/App.exe")
+        );
+        assert_eq!(
+            installer_url("https://cdn", "1.0.0", "windows-x86_64", r"nsis\App.exe"),
+            "https://cdn/nsis/App.exe"
+        );
+    }
+
+    #[test]
+    fn test_generate_options_builder_writes_and_returns_manifest() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("bundle");
+        write_file(&bundle_dir.join("app_3.1.0_amd64.AppImage"), "appimage");
+        write_file(&bundle_dir.join("app_3.1.0_amd64.AppImage.sig"), "sig");
+        let output_path = dir.join("dist").join("latest.json");
+
+        let manifest = GenerateOptions::new("https://example.com/downloads")
+            .notes("builder notes")
+            .bundle_dir(&bundle_dir)
+            .public_key("pubkey")
+            .output(&output_path)
+            .version("3.1.0")
+            .generate()
+            .unwrap();
+
+        assert_eq!(manifest.version, "3.1.0");
+        assert_eq!(manifest.notes, "builder notes");
+        let written: LatestJson =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        assert_eq!(written, manifest);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_generate_with_summary_reports_platforms_and_unsigned_installers() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("bundle");
+        write_file(&bundle_dir.join("app_1.0.0_amd64.AppImage"), "appimage");
+        write_file(&bundle_dir.join("app_1.0.0_amd64.AppImage.sig"), "sig");
+        write_file(&bundle_dir.join("app_1.0.0_x64.dmg"), "dmg");
+
+        let (_, summary) = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir(&bundle_dir)
+            .public_key("pubkey")
+            .output(dir.join("latest.json"))
+            .version("1.0.0")
+            .generate_with_summary()
+            .unwrap();
+
+        assert_eq!(
+            summary,
+            GenerationSummary {
+                version: "1.0.0".to_string(),
+                version_file: None,
+                platform_keys: vec!["linux-x86_64".to_string()],
+                installer_count: 2,
+                installers: vec![
+                    InstallerStatus {
+                        path: bundle_dir.join("app_1.0.0_amd64.AppImage"),
+                        platform_key: Some("linux-x86_64".to_string()),
+                        size: Some(8),
+                        signature: SignatureStatus::Present,
+                    },
+                    InstallerStatus {
+                        path: bundle_dir.join("app_1.0.0_x64.dmg"),
+                        platform_key: None,
+                        size: Some(3),
+                        signature: SignatureStatus::Missing,
+                    },
+                ],
+                unsigned: vec!["app_1.0.0_x64.dmg".to_string()],
+                warnings: vec![Warning::UnsignedInstaller {
+                    platform: "darwin-x86_64".to_string(),
+                    installer: "app_1.0.0_x64.dmg".to_string(),
+                }],
+            }
+        );
+        assert!(summary.warnings[0]
+            .to_string()
+            .ends_with("(Tauri does not sign .dmg files)"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_summary_warns_about_unknown_files_and_strict_platforms_rejects_them() {
+        let mock = MockFs::new()
+            .with_file("/bundle/a/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/a/app_1.0.0_x64_en-US.msi.sig", "sig")
+            .with_file("/bundle/b/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/b/app_1.0.0_x64_en-US.msi.sig", "sig")
+            .with_file("/bundle/notes.txt", "txt");
+
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .installer_extensions(["msi", "txt"])
+            .filesystem(mock);
+
+        let (_, summary) = options.clone().generate_with_summary().unwrap();
+
+        assert_eq!(
+            summary.warnings,
+            [
+                Warning::UnknownPlatform {
+                    installer: "notes.txt".to_string(),
+                },
+                Warning::DuplicatePlatform {
+                    platform: "windows-x86_64".to_string(),
+                    selected: "app_1.0.0_x64_en-US.msi".to_string(),
+                    skipped: vec!["app_1.0.0_x64_en-US.msi".to_string()],
+                },
+            ]
+        );
+        assert!(matches!(
+            options.strict_platforms(true).generate(),
+            Err(LatestJsonError::UnknownPlatform { installer }) if installer == "notes.txt"
+        ));
+    }
+
+    #[test]
+    fn test_app_name_scopes_discovery_to_one_product() {
+        let mock = MockFs::new()
+            .with_file("/bundle/msi/MyApp_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/msi/MyApp_1.0.0_x64_en-US.msi.sig", "a")
+            .with_file("/bundle/msi/MyAppAdmin_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/msi/MyAppAdmin_1.0.0_x64_en-US.msi.sig", "b")
+            .with_file("/bundle/macos/myapp.app.tar.gz", "tar")
+            .with_file("/bundle/macos/myapp.app.tar.gz.sig", "c")
+            .with_file("/bundle/macos/Other.app.tar.gz", "tar")
+            .with_file("/bundle/macos/Other.app.tar.gz.sig", "d");
+
+        let (latest, summary) = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .app_name("MyApp")
+            .filesystem(mock)
+            .generate_with_summary()
+            .unwrap();
+
+        assert_eq!(summary.installer_count, 2);
+        assert_eq!(latest.platforms["windows-x86_64"].signature, "a");
+        assert_eq!(latest.platforms["darwin-x86_64"].signature, "c");
+        assert_eq!(latest.platforms.len(), 2);
+    }
+
+    #[test]
+    fn test_signature_extension_pairs_custom_signature_files() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi.minisig", "minisig")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi.sig", "sig");
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .filesystem(mock);
+
+        let (default, _) = options.clone().generate_with_summary().unwrap();
+        assert_eq!(default.platforms["windows-x86_64"].signature, "sig");
+        let (custom, _) = options
+            .clone()
+            .signature_extension(".minisig")
+            .generate_with_summary()
+            .unwrap();
+        assert_eq!(custom.platforms["windows-x86_64"].signature, "minisig");
+
+        let multi_dot = MockFs::new()
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi.minisig.sig", "multi");
+        let (latest, summary) = options
+            .signature_extension("minisig.sig")
+            .filesystem(multi_dot)
+            .generate_with_summary()
+            .unwrap();
+        assert_eq!(latest.platforms["windows-x86_64"].signature, "multi");
+        assert!(summary.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_signatures_pair_by_exact_file_name_and_orphans_warn() {
+        let mock = MockFs::new()
+            .with_file("/bundle/msi/MyApp_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/msi/MyApp_1.0.0_x64_en-US.msi.sig", "mine")
+            .with_file("/bundle/msi/Other_1.0.0_x64_en-US.msi.sig", "other")
+            .with_file("/bundle/msi/MyApp_0.9.0_x64_en-US.msi.sig", "stale");
+        let (latest, summary) = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .filesystem(mock)
+            .generate_with_summary()
+            .unwrap();
+
+        assert_eq!(latest.platforms["windows-x86_64"].signature, "mine");
+        assert_eq!(
+            summary.warnings,
+            [
+                Warning::OrphanSignature {
+                    signature: "MyApp_0.9.0_x64_en-US.msi.sig".to_string()
+                },
+                Warning::OrphanSignature {
+                    signature: "Other_1.0.0_x64_en-US.msi.sig".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_per_platform_output_writes_one_manifest_per_file_name() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi.sig", "win")
+            .with_file("/bundle/app_1.0.0_arm64_en-US.msi", "msi")
+            .with_file("/bundle/app_1.0.0_arm64_en-US.msi.sig", "win-arm")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage.sig", "linux");
+        GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .notes("shared")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .output_mode(OutputMode::PerPlatform {
+                file_name: "latest-{{os}}.json".to_string(),
+            })
+            .backup(true)
+            .filesystem(mock.clone().with_file("/out/latest-linux.json", "previous"))
+            .generate()
+            .unwrap();
+
+        let read = |path: &str| -> LatestJson {
+            serde_json::from_slice(&mock.get(Path::new(path)).unwrap()).unwrap()
+        };
+        let windows = read("/out/latest-windows.json");
+        let keys: Vec<_> = windows.platforms.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["windows-aarch64", "windows-x86_64"]);
+        assert_eq!(windows.notes, "shared");
+        let linux = read("/out/latest-linux.json");
+        assert_eq!(linux.platforms["linux-x86_64"].signature, "linux");
+        assert_eq!(linux.version, "1.0.0");
+        assert!(mock.get(Path::new("/out/latest.json")).is_none());
+        assert_eq!(
+            mock.get(Path::new("/out/latest-linux.json.bak")).unwrap(),
+            b"previous"
+        );
+        assert!(mock
+            .get(Path::new("/out/latest-windows.json.bak"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_per_platform_output_ignores_merge_into_existing() {
+        let existing = r#"{"version":"0.9.0","pub_date":"2024-01-01T00:00:00Z","platforms":{"darwin-aarch64":{"signature":"old","url":"https://example.com/old.app.tar.gz"}}}"#;
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage.sig", "linux")
+            .with_file("/out/latest.json", existing);
+        GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .output_mode(OutputMode::PerPlatform {
+                file_name: DEFAULT_PER_PLATFORM_FILE_NAME.to_string(),
+            })
+            .merge_into_existing(true)
+            .filesystem(mock.clone())
+            .generate()
+            .unwrap();
+
+        let linux: LatestJson = serde_json::from_slice(
+            &mock
+                .get(Path::new("/out/latest-linux-x86_64.json"))
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(linux.version, "1.0.0");
+        assert!(!linux.extra.contains_key("versions"));
+        assert!(mock
+            .get(Path::new("/out/latest-darwin-aarch64.json"))
+            .is_none());
+        assert_eq!(
+            mock.get(Path::new("/out/latest.json")).unwrap(),
+            existing.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_progress_reports_each_installer_then_finished() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi.sig", "win")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage.sig", "linux");
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .progress(move |event| recorded.lock().unwrap().push(event))
+            .filesystem(mock)
+            .generate()
+            .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[4], ProgressEvent::Finished { count: 2 });
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, ProgressEvent::Verified { .. })));
+        for name in ["app_1.0.0_x64_en-US.msi", "app_1.0.0_amd64.AppImage"] {
+            let name = name.to_string();
+            let started = events
+                .iter()
+                .position(|e| *e == ProgressEvent::StartedInstaller { name: name.clone() });
+            let processed = events
+                .iter()
+                .position(|e| *e == ProgressEvent::Processed { name: name.clone() });
+            assert!(started.unwrap() < processed.unwrap());
+        }
+    }
+
+    #[test]
+    fn test_zipped_windows_archives_map_by_arch_and_pair_signatures() {
+        let mock = MockFs::new()
+            .with_file("/bundle/msi/app_1.0.0_arm64_en-US.msi", "msi")
+            .with_file("/bundle/msi/app_1.0.0_arm64_en-US.msi.zip", "arm-zip")
+            .with_file("/bundle/msi/app_1.0.0_arm64_en-US.msi.zip.sig", "arm-sig")
+            .with_file("/bundle/nsis/app_1.0.0_x64-setup.exe", "exe")
+            .with_file("/bundle/nsis/app_1.0.0_x64-setup.nsis.zip", "x64-zip")
+            .with_file("/bundle/nsis/app_1.0.0_x64-setup.nsis.zip.sig", "x64-sig");
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .filesystem(mock);
+
+        assert!(matches!(
+            options.clone().generate(),
+            Err(LatestJsonError::MissingSignature { .. })
+        ));
+        assert_eq!(
+            updater_archive(Path::new("/b/app_1.0.0_x64-setup.exe")),
+            Some(PathBuf::from("/b/app_1.0.0_x64-setup.nsis.zip"))
+        );
+
+        let latest = options
+            .manifest_format(ManifestFormat::V1)
+            .generate()
+            .unwrap();
+        let aarch64 = &latest.platforms["windows-aarch64"];
+        assert_eq!(aarch64.signature, "arm-sig");
+        assert!(aarch64.url.ends_with("/app_1.0.0_arm64_en-US.msi.zip"));
+        let x86_64 = &latest.platforms["windows-x86_64"];
+        assert_eq!(x86_64.signature, "x64-sig");
+        assert!(x86_64.url.ends_with("/app_1.0.0_x64-setup.nsis.zip"));
+    }
+
+    #[test]
+    fn test_v1_manifest_format_uses_updater_archives() {
+        let mock = MockFs::new()
+            .with_file("/bundle/msi/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/msi/app_1.0.0_x64_en-US.msi.sig", "msi-sig")
+            .with_file("/bundle/msi/app_1.0.0_x64_en-US.msi.zip", "zip")
+            .with_file("/bundle/msi/app_1.0.0_x64_en-US.msi.zip.sig", "zip-sig")
+            .with_file("/bundle/appimage/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file(
+                "/bundle/appimage/app_1.0.0_amd64.AppImage.sig",
+                "appimage-sig",
+            )
+            .with_file("/bundle/appimage/app_1.0.0_amd64.AppImage.tar.gz", "tgz")
+            .with_file(
+                "/bundle/appimage/app_1.0.0_amd64.AppImage.tar.gz.sig",
+                "tgz-sig",
+            );
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .filesystem(mock);
+
+        let (v2, _) = options.clone().generate_with_summary().unwrap();
+        assert_eq!(v2.platforms["windows-x86_64"].signature, "msi-sig");
+        assert_eq!(v2.platforms["linux-x86_64"].signature, "appimage-sig");
+        let (v1, _) = options
+            .manifest_format(ManifestFormat::V1)
+            .generate_with_summary()
+            .unwrap();
+        assert_eq!(v1.platforms["windows-x86_64"].signature, "zip-sig");
+        assert_eq!(
+            v1.platforms["linux-x86_64"].url,
+            "https://example.com/downloads/app_1.0.0_amd64.AppImage.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_verify_mode_requires_non_empty_signatures_unless_off() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi.sig", "\n")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_aarch64.dmg", "dmg")
+            .with_file("/bundle/app_1.0.0_aarch64.dmg.sig", "sig");
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .filesystem(mock);
+
+        assert!(matches!(
+            options.clone().generate(),
+            Err(LatestJsonError::MissingSignature { platform, .. }) if platform == "linux-x86_64"
+        ));
+        assert!(matches!(
+            options.clone().verify(VerifyMode::PresenceOnly).installer_extensions(["msi"]).generate(),
+            Err(LatestJsonError::EmptySignature { platform, .. }) if platform == "windows-x86_64"
+        ));
+
+        let (latest, summary) = options
+            .verify(VerifyMode::Off)
+            .generate_with_summary()
+            .unwrap();
+        assert_eq!(latest.platforms["windows-x86_64"].signature, "");
+        assert_eq!(latest.platforms["darwin-aarch64"].signature, "sig");
+        assert_eq!(summary.unsigned, ["app_1.0.0_amd64.AppImage"]);
+        assert_eq!(
+            summary.warnings[0].to_string(),
+            "skipped unsigned installer app_1.0.0_amd64.AppImage for linux-x86_64 (no signature file next to it)"
+        );
+    }
+
+    #[test]
+    fn test_require_all_signed_reports_installers_that_lost_selection() {
+        let mock = MockFs::new()
+            .with_file("/bundle/msi/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/msi/app_1.0.0_x64_en-US.msi.sig", "sig")
+            .with_file("/bundle/nsis/app_1.0.0_x64-setup.exe", "exe");
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .filesystem(mock);
+
+        options.clone().generate().unwrap();
+        match options.require_all_signed(true).generate() {
+            Err(LatestJsonError::UnsignedInstallers { paths }) => assert_eq!(
+                paths,
+                [PathBuf::from("/bundle/nsis/app_1.0.0_x64-setup.exe")]
+            ),
+            other => panic!("expected UnsignedInstallers, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_custom_verifier_replaces_minisign() {
+        struct ExactVerifier;
+
+        impl Verifier for ExactVerifier {
+            fn verify(
+                &self,
+                data: &[u8],
+                signature: &str,
+                public_key: &str,
+            ) -> std::result::Result<(), VerifyError> {
+                if public_key == "new-key" && signature.as_bytes() == data {
+                    Ok(())
+                } else {
+                    Err(VerifyError::new(format!("{} rejected", public_key)))
+                }
+            }
+        }
+
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_keys(["old-key", "new-key"])
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::Full)
+            .verifier(ExactVerifier);
+        let signed = MockFs::new()
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage.sig", "appimage");
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        let latest = options
+            .clone()
+            .progress(move |event| recorded.lock().unwrap().push(event))
+            .filesystem(signed)
+            .generate()
+            .unwrap();
+        assert_eq!(latest.platforms["linux-x86_64"].signature, "appimage");
+        assert!(events.lock().unwrap().contains(&ProgressEvent::Verified {
+            name: "app_1.0.0_amd64.AppImage".to_string()
+        }));
+
+        let tampered = MockFs::new()
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "tampered")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage.sig", "appimage");
+        match options.filesystem(tampered).generate() {
+            Err(LatestJsonError::SignatureVerificationFailed { reason, .. }) => assert_eq!(
+                reason,
+                "no public key verified it (old-key rejected; new-key rejected)"
+            ),
+            other => panic!(
+                "expected SignatureVerificationFailed, got {:?}",
+                other.map(|_| ())
+            ),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_installer_names_do_not_panic() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let non_utf8 = |name: &[u8]| Path::new("/bundle").join(std::ffi::OsStr::from_bytes(name));
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly);
+
+        // Not selected (the MSI wins), so it is harmless.
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi.sig", "sig")
+            .with_file(non_utf8(b"app_1.0.0_x64-setup-\xff.exe"), "exe");
+        let latest = options.clone().filesystem(mock).generate().unwrap();
+        assert_eq!(latest.platforms.len(), 1);
+
+        let installer = non_utf8(b"app_1.0.0_amd64_\xff.AppImage");
+        let mock = MockFs::new()
+            .with_file(&installer, "appimage")
+            .with_file(non_utf8(b"app_1.0.0_amd64_\xff.AppImage.sig"), "sig");
+        match options.filesystem(mock).generate() {
+            Err(LatestJsonError::NonUtf8FileName { path }) => assert_eq!(path, installer),
+            other => panic!("expected NonUtf8FileName, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_require_all_signed_lists_every_unsigned_installer() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi.sig", "sig")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_aarch64.dmg", "dmg");
+        let result = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::Off)
+            .require_all_signed(true)
+            .filesystem(mock)
+            .generate();
+
+        match result {
+            Err(LatestJsonError::UnsignedInstallers { paths }) => assert_eq!(
+                paths,
+                [
+                    PathBuf::from("/bundle/app_1.0.0_aarch64.dmg"),
+                    PathBuf::from("/bundle/app_1.0.0_amd64.AppImage"),
+                ]
+            ),
+            other => panic!("expected UnsignedInstallers, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_missing_sig_file_falls_back_to_platform_env_var() {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        let _env_guard = CWD_LOCK.lock().unwrap_or_else(|poison| poison.into_inner());
+        let sig = STANDARD.encode("untrusted comment: signature from tauri secret key\nRUS...\n");
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_armhf.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file("/bundle/app_1.0.0_x64_en-US.msi.sig", "file-sig");
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .filesystem(mock);
+        assert_eq!(signature_env_var("linux-armv7"), "TAURI_SIG_LINUX_ARMV7");
+
+        std::env::set_var("TAURI_SIG_LINUX_ARMV7", format!("{}\n", sig));
+        let plain = options.clone().generate();
+        std::env::set_var("TAURI_SIG_LINUX_ARMV7", STANDARD.encode(&sig));
+        let wrapped = options.clone().generate();
+        std::env::remove_var("TAURI_SIG_LINUX_ARMV7");
+
+        assert_eq!(plain.unwrap().platforms["linux-armv7"].signature, sig);
+        let wrapped = wrapped.unwrap();
+        assert_eq!(wrapped.platforms["linux-armv7"].signature, sig);
+        assert_eq!(wrapped.platforms["windows-x86_64"].signature, "file-sig");
+        assert!(matches!(
+            options.generate(),
+            Err(LatestJsonError::MissingSignature { platform, .. }) if platform == "linux-armv7"
+        ));
+    }
+
+    #[test]
+    fn test_validate_manifest_reports_what_the_updater_rejects() {
+        let valid: LatestJson = serde_json::from_value(json!({
+            "version": "1.0.0",
+            "pub_date": "2024-05-01T12:30:00Z",
+            "platforms": {
+                "linux-x86_64": { "signature": "sig", "url": "https://example.com/app.AppImage" }
+            }
+        }))
+        .unwrap();
+        validate_manifest(&valid).unwrap();
+
+        let with = |edit: fn(&mut LatestJson)| {
+            let mut latest = valid.clone();
+            edit(&mut latest);
+            validate_manifest(&latest).unwrap_err()
+        };
+        assert_eq!(
+            with(|l| l.version = "1.0".to_string()),
+            ValidationError::InvalidVersion("1.0".to_string())
+        );
+        assert_eq!(
+            with(|l| l.pub_date = "2024-05-01".to_string()),
+            ValidationError::InvalidPubDate("2024-05-01".to_string())
+        );
+        assert_eq!(with(|l| l.platforms.clear()), ValidationError::NoPlatforms);
+        let platform = "linux-x86_64".to_string();
+        assert_eq!(
+            with(|l| l.platforms.values_mut().for_each(|e| e.signature.clear())),
+            ValidationError::EmptySignature {
+                platform: platform.clone()
+            }
+        );
+        assert_eq!(
+            with(|l| l.platforms.values_mut().for_each(|e| e.url.clear())),
+            ValidationError::EmptyUrl { platform }
+        );
+    }
+
+    #[test]
+    fn test_dmg_is_left_out_when_bundle_has_mac_archive() {
+        let mock = MockFs::new()
+            .with_file("/bundle/dmg/app_1.0.0_aarch64.dmg", "dmg")
+            .with_file("/bundle/dmg/app_1.0.0_aarch64.dmg.sig", "dmg-sig")
+            .with_file("/bundle/dmg/app_1.0.0_x64.dmg", "dmg")
+            .with_file("/bundle/dmg/app_1.0.0_x64.dmg.sig", "dmg-sig")
+            .with_file("/bundle/macos/app_1.0.0_aarch64.app.tar.gz", "tar")
+            .with_file("/bundle/macos/app_1.0.0_aarch64.app.tar.gz.sig", "tar-sig");
+
+        let (latest, summary) = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .filesystem(mock)
+            .generate_with_summary()
+            .unwrap();
+
+        assert_eq!(
+            latest.platforms.keys().collect::<Vec<_>>(),
+            ["darwin-aarch64"]
+        );
+        assert_eq!(latest.platforms["darwin-aarch64"].signature, "tar-sig");
+        assert!(summary.warnings.is_empty());
+        assert_eq!(summary.installer_count, 3);
+    }
+
+    /// Fails the first `failures` reads of each file with `kind`.
+    struct FlakyFs {
+        inner: MockFs,
+        kind: std::io::ErrorKind,
+        failures: u32,
+        reads: Arc<Mutex<HashMap<PathBuf, u32>>>,
+    }
+
+    impl FileSystem for FlakyFs {
+        fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+            let mut reads = self.reads.lock().unwrap();
+            let count = reads.entry(path.to_path_buf()).or_default();
+            *count += 1;
+            if *count <= self.failures {
+                return Err(std::io::Error::new(self.kind, "flaky"));
+            }
+            self.inner.read(path)
+        }
+
+        fn write(&self, path: &Path, contents: &[u8]) -> std::io::Result<()> {
+            self.inner.write(path, contents)
+        }
+
+        fn walk(&self, dir: &Path, options: WalkOptions) -> std::io::Result<Vec<PathBuf>> {
+            self.inner.walk(dir, options)
+        }
+
+        fn exists(&self, path: &Path) -> bool {
+            self.inner.exists(path)
+        }
+    }
+
+    #[test]
+    fn test_read_retries_retry_transient_errors_only() {
+        let flaky = |kind, failures| FlakyFs {
+            inner: MockFs::new()
+                .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+                .with_file("/bundle/app_1.0.0_amd64.AppImage.sig", "sig"),
+            kind,
+            failures,
+            reads: Arc::default(),
+        };
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly);
+
+        let timed_out = std::io::ErrorKind::TimedOut;
+        assert!(options
+            .clone()
+            .filesystem(flaky(timed_out, 1))
+            .generate()
+            .is_err());
+        let latest = options
+            .clone()
+            .read_retries(2)
+            .filesystem(flaky(timed_out, 2))
+            .generate()
+            .unwrap();
+        assert_eq!(latest.platforms["linux-x86_64"].signature, "sig");
+
+        let not_found = flaky(std::io::ErrorKind::NotFound, 1);
+        let reads = Arc::clone(&not_found.reads);
+        assert!(matches!(
+            options.read_retries(3).filesystem(not_found).generate(),
+            Err(LatestJsonError::File { source, .. })
+                if matches!(&*source, LatestJsonError::Io(e) if e.kind() == std::io::ErrorKind::NotFound)
+        ));
+        assert_eq!(reads.lock().unwrap().values().sum::<u32>(), 1);
+    }
+
+    #[test]
+    fn test_write_latest_json_replaces_file_without_leaving_temp_files() {
+        let dir = make_temp_dir();
+        let output_path = dir.join("latest.json");
+        write_file(&output_path, "stale");
+
+        let latest = manifest(
+            json!({ "version": "1.0.0", "pub_date": "2024-05-01T12:30:00Z", "platforms": {} }),
+        );
+        write_latest_json(&RealFs, &latest, &output_path, true).unwrap();
+
+        let written: LatestJson =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        assert_eq!(written, latest);
+        let names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names, ["latest.json"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pretty_false_writes_compact_json() {
+        let dir = make_temp_dir();
+        let output_path = dir.join("latest.json");
+        let latest = manifest(
+            json!({ "version": "1.0.0", "pub_date": "2024-05-01T12:30:00Z", "platforms": {} }),
+        );
+
+        write_latest_json_with_options(
+            latest.clone(),
+            &output_path,
+            &GenerateOptions::new("https://example.com").pretty(false),
+        )
+        .unwrap();
+
+        let written = fs::read_to_string(&output_path).unwrap();
+        assert!(!written.contains('\n'));
+        assert_eq!(
+            serde_json::from_str::<LatestJson>(&written).unwrap(),
+            latest
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_expect_platforms_fails_or_warns_when_missing() {
+        let dir = make_temp_dir();
+        let output_path = dir.join("latest.json");
+        let latest = manifest(json!({
+            "version": "1.0.0",
+            "pub_date": "2024-05-01T12:30:00Z",
+            "platforms": { "linux-x86_64": { "signature": "sig", "url": "https://cdn/app.AppImage" } }
+        }));
+        let options = GenerateOptions::new("https://example.com")
+            .expect_platforms(["linux-x86_64", "windows-x86_64"]);
+
+        match write_latest_json_with_options(latest.clone(), &output_path, &options) {
+            Err(LatestJsonError::MissingExpectedPlatform { missing }) => {
+                assert_eq!(missing, ["windows-x86_64"])
+            }
+            other => panic!("expected MissingExpectedPlatform, got {:?}", other),
+        }
+        assert!(!output_path.exists());
+
+        write_latest_json_with_options(
+            latest,
+            &output_path,
+            &options.allow_missing_platforms(true),
+        )
+        .unwrap();
+        assert!(output_path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_generate_runs_against_mock_filesystem() {
+        let mock = MockFs::new()
+            .with_file("/bundle/msi/app_1.0.0_x64_en-US.msi", "msi")
+            .with_file(
+                "/bundle/msi/app_1.0.0_x64_en-US.msi.sig",
+                "\u{feff}msi-sig\n",
+            )
+            .with_file("/bundle/macos/app_1.0.0_aarch64.app.tar.gz", "archive")
+            .with_file("/bundle/macos/app_1.0.0_aarch64.app.tar.gz.sig", "mac-sig")
+            .with_file("/bundle/dmg/app_1.0.0_aarch64.dmg", "dmg");
+
+        let manifest = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .include_checksums(true)
+            .include_size(true)
+            .filesystem(mock.clone())
+            .generate()
+            .unwrap();
+
+        let keys: Vec<_> = manifest.platforms.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["darwin-aarch64", "windows-x86_64"]);
+        assert_eq!(manifest.platforms["windows-x86_64"].signature, "msi-sig");
+        assert_eq!(
+            manifest.platforms["darwin-aarch64"].url,
+            "https://example.com/downloads/app_1.0.0_aarch64.app.tar.gz"
+        );
+        assert!(manifest.platforms["windows-x86_64"].sha256.is_some());
+        assert_eq!(manifest.platforms["darwin-aarch64"].size, Some(7));
+        let written: LatestJson =
+            serde_json::from_slice(&mock.get(Path::new("/out/latest.json")).unwrap()).unwrap();
+        assert_eq!(written, manifest);
+    }
+
+    #[test]
+    fn test_check_minimum_version_requires_semver_not_above_version() {
+        assert_eq!(check_minimum_version("v1.1.0", "1.2.0").unwrap(), "1.1.0");
+        assert_eq!(check_minimum_version("1.2.0", "1.2.0").unwrap(), "1.2.0");
+        for minimum in ["1.3.0", "1.x", ""] {
+            assert!(matches!(
+                check_minimum_version(minimum, "1.2.0"),
+                Err(LatestJsonError::InvalidMinimumVersion { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_critical_and_minimum_version_are_written_to_manifest() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.2.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.2.0_amd64.AppImage.sig", "sig");
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.2.0")
+            .output("/out/latest.json")
+            .filesystem(mock.clone());
+
+        let plain = options.clone().generate().unwrap();
+        assert!(!plain.critical);
+        let written: Value =
+            serde_json::from_slice(&mock.get(Path::new("/out/latest.json")).unwrap()).unwrap();
+        assert!(written.get("critical").is_none());
+        assert!(written.get("minimum_version").is_none());
+
+        options
+            .clone()
+            .critical(true)
+            .minimum_version("1.1.0")
+            .generate()
+            .unwrap();
+        let written: Value =
+            serde_json::from_slice(&mock.get(Path::new("/out/latest.json")).unwrap()).unwrap();
+        assert_eq!(written["critical"], true);
+        assert_eq!(written["minimum_version"], "1.1.0");
+        assert!(matches!(
+            options.minimum_version("2.0.0").generate(),
+            Err(LatestJsonError::InvalidMinimumVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_preserve_relative_path_keeps_bundle_subdirectories_in_urls() {
+        let mock = MockFs::new()
+            .with_file("/bundle/nsis/My App_1.0.0_x64-setup.exe", "exe")
+            .with_file("/bundle/nsis/My App_1.0.0_x64-setup.exe.sig", "nsis-sig");
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .filesystem(mock);
+
+        let flat = options.clone().generate().unwrap();
+        let nested = options.preserve_relative_path(true).generate().unwrap();
+
+        assert_eq!(
+            flat.platforms["windows-x86_64"].url,
+            "https://example.com/downloads/My%20App_1.0.0_x64-setup.exe"
+        );
+        assert_eq!(
+            nested.platforms["windows-x86_64"].url,
+            "https://example.com/downloads/nsis/My%20App_1.0.0_x64-setup.exe"
+        );
+    }
+
+    #[test]
+    fn test_channel_output_path_separates_pre_releases() {
+        let output = Path::new("dist/latest.json");
+        let mut channels = HashMap::new();
+
+        assert_eq!(
+            channel_output_path(output, "1.2.0", &channels),
+            Path::new("dist/latest.json")
+        );
+        assert_eq!(
+            channel_output_path(output, "1.2.0-beta.3", &channels),
+            Path::new("dist/latest-beta.json")
+        );
+        assert_eq!(
+            channel_output_path(output, "1.2.0-rc1+build.5", &channels),
+            Path::new("dist/latest-rc1.json")
+        );
+        channels.insert("beta".to_string(), PathBuf::from("beta/updates.json"));
+        assert_eq!(
+            channel_output_path(output, "1.2.0-beta.3", &channels),
+            Path::new("beta/updates.json")
+        );
+    }
+
+    #[test]
+    fn test_url_overrides_replace_joined_urls_per_platform() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_aarch64.app.tar.gz", "archive")
+            .with_file("/bundle/app_1.0.0_aarch64.app.tar.gz.sig", "mac-sig")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage.sig", "sig");
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .filesystem(mock);
+        let overrides =
+            |url: &str| HashMap::from([("darwin-aarch64".to_string(), url.to_string())]);
+
+        let manifest = options
+            .clone()
+            .url_overrides(overrides(
+                "https://mac-mirror.example.com/notarized/App.app.tar.gz",
+            ))
+            .generate()
+            .unwrap();
+
+        assert_eq!(
+            manifest.platforms["darwin-aarch64"].url,
+            "https://mac-mirror.example.com/notarized/App.app.tar.gz"
+        );
+        assert_eq!(
+            manifest.platforms["linux-x86_64"].url,
+            "https://example.com/downloads/app_1.0.0_amd64.AppImage"
+        );
+        assert!(matches!(
+            options
+                .url_overrides(overrides("ftp://mirror/app.tar.gz"))
+                .generate(),
+            Err(LatestJsonError::InvalidDownloadUrl { .. })
+        ));
+    }
+
+    #[test]
+    fn test_channel_aware_generation_writes_beta_manifest() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.2.0-beta.3_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.2.0-beta.3_amd64.AppImage.sig", "sig");
+
+        GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.2.0-beta.3")
+            .output("/out/latest.json")
+            .channel_aware(true)
+            .filesystem(mock.clone())
+            .generate()
+            .unwrap();
+
+        assert!(mock.get(Path::new("/out/latest-beta.json")).is_some());
+        assert!(mock.get(Path::new("/out/latest.json")).is_none());
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs::{create_dir_all, File};
-    use std::io::Write;
-    use std::sync::atomic::{AtomicU64, Ordering};
-    #[cfg(not(feature = "verify-signature"))]
-    use std::sync::Mutex;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    #[test]
+    fn test_backup_keeps_previous_manifest() {
+        let mock = MockFs::new().with_file("/out/latest.json", "previous");
+        let latest = manifest(
+            json!({ "version": "1.1.0", "pub_date": "2024-05-01T12:30:00Z", "platforms": {} }),
+        );
+        let options = GenerateOptions::new("https://example.com")
+            .filesystem(mock.clone())
+            .backup(true);
 
-    #[cfg(not(feature = "verify-signature"))]
-    static CWD_LOCK: Mutex<()> = Mutex::new(());
-    static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+        write_latest_json_with_options(latest, Path::new("/out/latest.json"), &options).unwrap();
 
-    #[cfg(not(feature = "verify-signature"))]
-    struct CurrentDirGuard {
-        original: PathBuf,
+        assert_eq!(
+            mock.get(Path::new("/out/latest.json.bak")).unwrap(),
+            b"previous"
+        );
+        let written: LatestJson =
+            serde_json::from_slice(&mock.get(Path::new("/out/latest.json")).unwrap()).unwrap();
+        assert_eq!(written.version, "1.1.0");
     }
 
-    #[cfg(not(feature = "verify-signature"))]
-    impl CurrentDirGuard {
-        fn switch_to(path: &Path) -> Self {
-            let original = std::env::current_dir().unwrap();
-            std::env::set_current_dir(path).unwrap();
-            Self { original }
-        }
-    }
+    #[test]
+    fn test_overwrite_false_refuses_existing_outputs() {
+        let latest = manifest(json!({
+            "version": "1.1.0",
+            "pub_date": "2024-05-01T12:30:00Z",
+            "platforms": {
+                "linux-x86_64": { "signature": "sig", "url": "https://example.com/app.AppImage" }
+            }
+        }));
+        let output_path = Path::new("/out/latest.json");
+        let mock = MockFs::new().with_file("/out/latest-linux-x86_64.json", "previous");
+        let options = GenerateOptions::new("https://example.com")
+            .filesystem(mock.clone())
+            .overwrite(false);
 
-    #[cfg(not(feature = "verify-signature"))]
-    impl Drop for CurrentDirGuard {
-        fn drop(&mut self) {
-            std::env::set_current_dir(&self.original).unwrap();
+        write_latest_json_with_options(latest.clone(), output_path, &options).unwrap();
+        assert!(mock.get(output_path).is_some());
+        match write_latest_json_with_options(latest.clone(), output_path, &options) {
+            Err(LatestJsonError::OutputExists { path }) => assert_eq!(path, output_path),
+            other => panic!("expected OutputExists, got {:?}", other.map(|_| ())),
         }
-    }
 
-    fn make_temp_dir() -> PathBuf {
-        let mut base = std::env::temp_dir();
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        let seq = TEST_COUNTER.fetch_add(1, Ordering::Relaxed);
-        base.push(format!(
-            "tauri-latest-json-test-{}-{}-{}",
-            std::process::id(),
-            nanos,
-            seq
+        let per_platform = options.output_mode(OutputMode::PerPlatform {
+            file_name: DEFAULT_PER_PLATFORM_FILE_NAME.to_string(),
+        });
+        assert!(matches!(
+            write_latest_json_with_options(latest, output_path, &per_platform),
+            Err(LatestJsonError::OutputExists { path }) if path == Path::new("/out/latest-linux-x86_64.json")
         ));
-        create_dir_all(&base).unwrap();
-        base
+        assert_eq!(
+            mock.get(Path::new("/out/latest-linux-x86_64.json"))
+                .unwrap(),
+            b"previous"
+        );
     }
 
-    fn write_file(path: &Path, content: &str) {
-        if let Some(parent) = path.parent() {
-            create_dir_all(parent).unwrap();
-        }
-        std::fs::write(path, content).unwrap();
+    #[test]
+    fn test_dry_run_does_not_write_output() {
+        let dir = make_temp_dir();
+        let output_path = dir.join("latest.json");
+
+        write_latest_json_with_options(
+            manifest(
+                json!({ "version": "1.0.0", "pub_date": "2024-05-01T12:30:00Z", "platforms": {} }),
+            ),
+            &output_path,
+            &GenerateOptions::new("https://example.com").dry_run(true),
+        )
+        .unwrap();
+
+        assert!(!output_path.exists());
+        fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
-    fn test_detect_platform_key_variants() {
-        assert_eq!(
-            detect_platform_key("app_0.1.0_x64_en-US.msi"),
-            "windows-x86_64"
-        );
-        assert_eq!(
-            detect_platform_key("app_0.1.0_x64_en-US.exe"),
-            "windows-x86_64"
+    fn test_build_latest_json_pairs_signature_with_selected_installer() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("bundle");
+        write_file(&dir.join("package.json"), r#"{"version":"1.0.0"}"#);
+        write_file(
+            &bundle_dir.join("msi").join("app_1.0.0_x64_en-US.msi"),
+            "msi",
         );
-        assert_eq!(detect_platform_key("app_0.1.0_x64.dmg"), "darwin-x86_64");
-        assert_eq!(detect_platform_key("app_0.1.0_arm64.dmg"), "darwin-aarch64");
-        assert_eq!(
-            detect_platform_key("app_0.1.0_aarch64.app.tar.gz"),
-            "darwin-aarch64"
+        write_file(
+            &bundle_dir.join("msi").join("app_1.0.0_x64_en-US.msi.sig"),
+            "msi-sig",
         );
-        assert_eq!(
-            detect_platform_key("app_0.1.0_x64.app.tar.gz"),
-            "darwin-x86_64"
+        write_file(
+            &bundle_dir.join("nsis").join("app_1.0.0_x64-setup.exe"),
+            "exe",
         );
-        assert_eq!(
-            detect_platform_key("AppImage-0.1.0-x86_64.AppImage"),
-            "linux-x86_64"
+        write_file(
+            &bundle_dir.join("nsis").join("app_1.0.0_x64-setup.exe.sig"),
+            "exe-sig",
         );
-        assert_eq!(
-            detect_platform_key("AppImage-0.1.0-arm64.AppImage"),
-            "linux-aarch64"
+
+        let latest = build_latest_json_for_project(
+            &bundle_dir,
+            "pubkey",
+            &dir,
+            &GenerateOptions::new("https://example.com").notes("notes"),
+            &mut |_| None,
+        )
+        .unwrap();
+
+        let entry = &latest.platforms["windows-x86_64"];
+        assert_eq!(entry.signature, "msi-sig");
+        assert_eq!(entry.url, "https://example.com/app_1.0.0_x64_en-US.msi");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_latest_json_strips_bom_and_whitespace_from_signature() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("bundle");
+        write_file(&dir.join("package.json"), r#"{"version":"1.0.0"}"#);
+        write_file(&bundle_dir.join("app_1.0.0_amd64.AppImage"), "appimage");
+        write_file(
+            &bundle_dir.join("app_1.0.0_amd64.AppImage.sig"),
+            "\u{feff}dW50cnVzdGVk\n",
         );
-        assert_eq!(detect_platform_key("app_0.1.0_amd64.deb"), "linux-x86_64");
+
+        let latest = build_latest_json_for_project(
+            &bundle_dir,
+            "pubkey",
+            &dir,
+            &GenerateOptions::new("https://example.com").notes("notes"),
+            &mut |_| None,
+        )
+        .unwrap();
+
+        assert_eq!(latest.platforms["linux-x86_64"].signature, "dW50cnVzdGVk");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_tauri_conf_walks_up_to_src_tauri() {
+        let dir = make_temp_dir();
+        let start = dir.join("frontend").join("src");
+        create_dir_all(&start).unwrap();
+        write_file(&dir.join("src-tauri").join("tauri.conf.json"), "{}");
+
         assert_eq!(
-            detect_platform_key("app_0.1.0_aarch64.rpm"),
-            "linux-aarch64"
+            find_tauri_conf(&start).unwrap(),
+            dir.join("src-tauri").join("tauri.conf.json")
         );
-        assert_eq!(detect_platform_key("app-0.1.0-x64.tar.gz"), "linux-x86_64");
-        assert_eq!(detect_platform_key("unknown.bin"), "unknown");
+
+        let empty = make_temp_dir();
+        match find_tauri_conf(&empty) {
+            Err(LatestJsonError::ConfigNotFound { searched }) => {
+                assert!(searched.contains(&empty.join("src-tauri").join("tauri.conf.json")));
+            }
+            // An ancestor of the temp dir may legitimately hold a config.
+            Ok(path) => assert!(!path.starts_with(&empty)),
+            Err(e) => panic!("unexpected error: {e}"),
+        }
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&empty).unwrap();
     }
 
     #[test]
-    fn test_read_version_prefers_package_json() {
+    fn test_read_version_from_git_uses_latest_tag() {
         let dir = make_temp_dir();
-        {
-            let mut f = File::create(dir.join("Cargo.toml")).unwrap();
-            writeln!(
-                f,
-                "[package]\nname = \"dummy\"\nversion = \"0.2.0\"\n\n[dependencies]\n"
-            )
-            .unwrap();
-        }
-        {
-            let mut f = File::create(dir.join("package.json")).unwrap();
-            writeln!(f, "{{\"name\":\"dummy\",\"version\":\"1.2.3\"}}").unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(&dir)
+                .output()
+        };
+        if git(&["init", "-q"]).is_err() {
+            // git is not installed; nothing to test.
+            fs::remove_dir_all(&dir).unwrap();
+            return;
         }
-        let v = read_version_from_dir(&dir).unwrap();
-        assert_eq!(v, "1.2.3");
-        std::fs::remove_dir_all(&dir).ok();
+
+        assert!(matches!(
+            read_version_from_git(&dir),
+            Err(LatestJsonError::GitVersion(_))
+        ));
+        git(&["commit", "-q", "--allow-empty", "-m", "init"]).unwrap();
+        git(&["tag", "v1.4.0"]).unwrap();
+        assert_eq!(read_version_from_git(&dir).unwrap(), "1.4.0");
+        fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
-    fn test_read_version_from_cargo_toml() {
+    fn test_generated_platforms_are_sorted() {
         let dir = make_temp_dir();
-        {
-            let mut f = File::create(dir.join("Cargo.toml")).unwrap();
-            writeln!(
-                f,
-                "[package]\nname = \"dummy\"\nversion = \"9.9.9\"\n\n[dependencies]\n"
-            )
-            .unwrap();
+        let bundle_dir = dir.join("bundle");
+        write_file(&dir.join("package.json"), r#"{"version":"1.0.0"}"#);
+        for name in [
+            "app_1.0.0_x64_en-US.msi",
+            "app_1.0.0_amd64.AppImage",
+            "app_aarch64.app.tar.gz",
+            "app_1.0.0_arm64_en-US.msi",
+        ] {
+            write_file(&bundle_dir.join(name), "installer");
+            write_file(&bundle_dir.join(format!("{}.sig", name)), "sig");
         }
-        let v = read_version_from_dir(&dir).unwrap();
-        assert_eq!(v, "9.9.9");
-        std::fs::remove_dir_all(&dir).ok();
+        let output_path = dir.join("latest.json");
+
+        generate_for_project(
+            &bundle_dir,
+            "pubkey",
+            "https://example.com",
+            "notes",
+            &dir,
+            &output_path,
+        )
+        .unwrap();
+
+        let written = fs::read_to_string(&output_path).unwrap();
+        let positions: Vec<usize> = [
+            "darwin-aarch64",
+            "linux-x86_64",
+            "windows-aarch64",
+            "windows-x86_64",
+        ]
+        .iter()
+        .map(|key| written.find(&format!("\"{}\"", key)).unwrap())
+        .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Parses a test manifest written as JSON.
+    fn manifest(value: Value) -> LatestJson {
+        serde_json::from_value(value).unwrap()
     }
 
     #[test]
-    fn test_read_version_from_src_tauri_cargo_toml() {
+    fn test_merge_latest_json_same_version_keeps_existing_platforms() {
+        let existing = manifest(json!({
+            "version": "1.0.0",
+            "pub_date": "2024-05-01T12:30:00Z",
+            "platforms": {
+                "darwin-aarch64": { "signature": "mac", "url": "https://old/mac" },
+                "windows-x86_64": { "signature": "old", "url": "https://old/win" }
+            }
+        }));
+        let latest = manifest(json!({
+            "version": "1.0.0",
+            "pub_date": "2024-05-02T12:30:00Z",
+            "platforms": { "windows-x86_64": { "signature": "new", "url": "https://new/win" } }
+        }));
+
+        let merged = merge_latest_json(existing, latest).unwrap();
+        assert_eq!(merged.platforms["darwin-aarch64"].signature, "mac");
+        assert_eq!(merged.platforms["windows-x86_64"].signature, "new");
+        assert_eq!(merged.pub_date, "2024-05-02T12:30:00Z");
+        assert!(!merged.extra.contains_key("versions"));
+    }
+
+    #[test]
+    fn test_merge_latest_json_carries_custom_fields_forward() {
+        let existing = manifest(json!({
+            "version": "1.0.0",
+            "pub_date": "2024-05-01T12:30:00Z",
+            "platforms": {
+                "windows-x86_64": { "signature": "old", "url": "https://old/win", "with_elevated_task": true }
+            },
+            "homepage": "https://example.com",
+            "signature_algorithm": "minisign"
+        }));
+        let latest = manifest(json!({
+            "version": "1.1.0",
+            "pub_date": "2024-05-02T12:30:00Z",
+            "platforms": { "windows-x86_64": { "signature": "new", "url": "https://new/win" } },
+            "signature_algorithm": "ed25519"
+        }));
+
+        let merged = merge_latest_json(existing, latest).unwrap();
+        assert_eq!(merged.extra["homepage"], "https://example.com");
+        assert_eq!(merged.extra["signature_algorithm"], "ed25519");
+        let entry = &merged.platforms["windows-x86_64"];
+        assert_eq!(entry.signature, "new");
+        assert_eq!(entry.url, "https://new/win");
+        assert_eq!(entry.extra["with_elevated_task"], true);
+    }
+
+    #[test]
+    fn test_merge_latest_json_new_version_appends_history() {
+        let existing = manifest(json!({
+            "version": "1.1.0",
+            "pub_date": "2024-05-01T12:30:00Z",
+            "platforms": { "linux-x86_64": { "signature": "b", "url": "u" } },
+            "versions": [{ "version": "1.0.0", "platforms": {} }]
+        }));
+        let latest = manifest(json!({
+            "version": "1.2.0",
+            "pub_date": "2024-05-02T12:30:00Z",
+            "platforms": {}
+        }));
+
+        let merged = merge_latest_json(existing, latest).unwrap();
+        assert_eq!(merged.version, "1.2.0");
+        let versions = merged.extra["versions"].as_array().unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0]["version"], "1.1.0");
+        assert_eq!(versions[0]["platforms"]["linux-x86_64"]["signature"], "b");
+        assert!(versions[0].get("versions").is_none());
+        assert_eq!(versions[1]["version"], "1.0.0");
+    }
+
+    #[test]
+    fn test_latest_json_round_trips_unknown_fields() {
+        let value = json!({
+            "version": "1.0.0",
+            "notes": "n",
+            "pub_date": "2024-05-01T12:30:00Z",
+            "platforms": {
+                "linux-x86_64": { "signature": "s", "url": "u", "with_elevated_task": true }
+            },
+            "custom": { "channel": "beta" }
+        });
+
+        let parsed = manifest(value.clone());
+        assert_eq!(parsed.extra["custom"]["channel"], "beta");
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+
         let dir = make_temp_dir();
-        create_dir_all(dir.join("src-tauri")).unwrap();
-        {
-            let mut f = File::create(dir.join("src-tauri").join("Cargo.toml")).unwrap();
-            writeln!(
-                f,
-                "[package]\nname = \"dummy\"\nversion = \"3.4.5\"\n\n[dependencies]\n"
-            )
-            .unwrap();
-        }
-        let v = read_version_from_dir(&dir).unwrap();
-        assert_eq!(v, "3.4.5");
+        let path = dir.join("latest.json");
+        write_file(&path, &value.to_string());
+        assert_eq!(LatestJson::from_file(&path).unwrap(), parsed);
+        write_file(&path, "{}");
+        assert!(matches!(
+            LatestJson::from_file(&path),
+            Err(LatestJsonError::File { path: p, source })
+                if p == path && matches!(*source, LatestJsonError::Json(_))
+        ));
+        let missing = dir.join("missing.json");
+        assert!(matches!(
+            LatestJson::from_file(&missing),
+            Err(LatestJsonError::File { path, source })
+                if path == missing && matches!(*source, LatestJsonError::Io(_))
+        ));
         std::fs::remove_dir_all(&dir).ok();
     }
 
-    #[cfg(not(feature = "verify-signature"))]
+    #[test]
+    fn test_normalize_version_strips_v_and_enforces_semver() {
+        assert_eq!(normalize_version("v1.2.3", true).unwrap(), "1.2.3");
+        assert_eq!(
+            normalize_version("1.2.0-beta.1+build.5", true).unwrap(),
+            "1.2.0-beta.1+build.5"
+        );
+        assert!(matches!(
+            normalize_version("1.0", true),
+            Err(LatestJsonError::InvalidVersion { found }) if found == "1.0"
+        ));
+        assert_eq!(normalize_version("v2024.10", false).unwrap(), "2024.10");
+    }
+
     #[test]
     fn test_generate_latest_json_writes_expected_structure() {
         let dir = make_temp_dir();
@@ -651,12 +4436,13 @@ mod tests {
         write_file(&bundle_dir.join("app_1.2.3_arm64.dmg"), "mac installer");
         write_file(&bundle_dir.join("app_1.2.3_arm64.dmg.sig"), "mac-signature");
 
-        generate_latest_json_for_project(
+        generate_for_project(
             &bundle_dir,
             "unused-public-key",
             "https://example.com/downloads",
             "release notes",
             &dir,
+            &dir.join("latest.json"),
         )
         .unwrap();
 
@@ -685,7 +4471,6 @@ mod tests {
         std::fs::remove_dir_all(&dir).ok();
     }
 
-    #[cfg(not(feature = "verify-signature"))]
     #[test]
     fn test_generate_latest_json_prefers_mac_updater_archive_over_dmg() {
         let dir = make_temp_dir();
@@ -704,12 +4489,13 @@ mod tests {
             "mac-signature",
         );
 
-        generate_latest_json_for_project(
+        generate_for_project(
             &bundle_dir,
             "unused-public-key",
             "https://example.com/downloads",
             "release notes",
             &dir,
+            &dir.join("latest.json"),
         )
         .unwrap();
 
@@ -726,6 +4512,121 @@ mod tests {
         std::fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_generate_latest_json_creates_output_parent_dirs() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("target").join("release").join("bundle");
+        write_file(
+            &dir.join("package.json"),
+            r#"{"name":"dummy","version":"1.2.3"}"#,
+        );
+        write_file(
+            &bundle_dir.join("app_1.2.3_x64_en-US.msi"),
+            "windows installer",
+        );
+        write_file(
+            &bundle_dir.join("app_1.2.3_x64_en-US.msi.sig"),
+            "windows-signature",
+        );
+        let output_path = bundle_dir.join("upload").join("latest.json");
+
+        generate_for_project(
+            &bundle_dir,
+            "unused-public-key",
+            "https://example.com/downloads",
+            "release notes",
+            &dir,
+            &output_path,
+        )
+        .unwrap();
+
+        assert!(output_path.exists());
+        assert!(!dir.join("latest.json").exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_build_latest_json_returns_manifest_without_writing() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("target").join("release").join("bundle");
+        write_file(
+            &dir.join("package.json"),
+            r#"{"name":"dummy","version":"1.2.3"}"#,
+        );
+        write_file(&bundle_dir.join("app_1.2.3_amd64.AppImage"), "appimage");
+        write_file(
+            &bundle_dir.join("app_1.2.3_amd64.AppImage.sig"),
+            "linux-signature",
+        );
+
+        let latest = build_latest_json_for_project(
+            &bundle_dir,
+            "unused-public-key",
+            &dir,
+            &GenerateOptions::new("https://example.com/downloads").notes("release notes"),
+            &mut |_| None,
+        )
+        .unwrap();
+
+        assert_eq!(latest.version, "1.2.3");
+        assert_eq!(
+            latest.platforms["linux-x86_64"].signature,
+            "linux-signature"
+        );
+        assert!(!dir.join("latest.json").exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_generate_latest_json_consults_mapper_before_default_detection() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("target").join("release").join("bundle");
+        write_file(
+            &dir.join("package.json"),
+            r#"{"name":"dummy","version":"1.2.3"}"#,
+        );
+        write_file(&bundle_dir.join("app_1.2.3_armhf.deb"), "armv7 deb");
+        write_file(&bundle_dir.join("app_1.2.3_armhf.deb.sig"), "armv7-sig");
+        write_file(&bundle_dir.join("app_1.2.3_x64_en-US.msi"), "msi");
+        write_file(&bundle_dir.join("app_1.2.3_x64_en-US.msi.sig"), "msi-sig");
+
+        let latest = build_latest_json_for_project(
+            &bundle_dir,
+            "unused-public-key",
+            &dir,
+            &GenerateOptions::new("https://example.com/downloads").notes("release notes"),
+            &mut |name: &str| name.contains("armhf").then(|| "linux-armv7".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(latest.platforms["linux-armv7"].signature, "armv7-sig");
+        assert_eq!(latest.platforms["windows-x86_64"].signature, "msi-sig");
+        assert!(!latest.platforms.contains_key("linux-x86_64"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_build_latest_json_uses_version_override_verbatim() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("target").join("release").join("bundle");
+        write_file(&bundle_dir.join("app_1.2.3_x64_en-US.msi"), "msi");
+        write_file(&bundle_dir.join("app_1.2.3_x64_en-US.msi.sig"), "msi-sig");
+
+        // No package.json/Cargo.toml: detection would fail without the override.
+        let latest = build_latest_json_for_project(
+            &bundle_dir,
+            "unused-public-key",
+            &dir,
+            &GenerateOptions::new("https://example.com/downloads")
+                .notes("release notes")
+                .version("1.2.3+ci.42"),
+            &mut |_| None,
+        )
+        .unwrap();
+        assert_eq!(latest.version, "1.2.3+ci.42");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_generate_latest_json_returns_error_when_no_installers() {
         let dir = make_temp_dir();
@@ -736,14 +4637,16 @@ mod tests {
             r#"{"name":"dummy","version":"1.2.3"}"#,
         );
 
-        let err = generate_latest_json_for_project(
+        let err = generate_for_project(
             &bundle_dir,
             "unused-public-key",
             "https://example.com/downloads",
             "release notes",
             &dir,
+            &dir.join("latest.json"),
         )
         .unwrap_err();
+        assert!(matches!(err, LatestJsonError::NoInstallers { .. }));
         assert!(err.to_string().contains("No installers found"));
         std::fs::remove_dir_all(&dir).ok();
     }
@@ -761,21 +4664,26 @@ mod tests {
             "windows installer",
         );
 
-        let err = generate_latest_json_for_project(
+        let err = generate_for_project(
             &bundle_dir,
             "unused-public-key",
             "https://example.com/downloads",
             "release notes",
             &dir,
+            &dir.join("latest.json"),
         )
         .unwrap_err();
+        assert!(matches!(
+            &err,
+            LatestJsonError::MissingSignature { platform, installer }
+                if platform == "windows-x86_64" && installer.ends_with("app_1.2.3_x64_en-US.msi")
+        ));
         assert!(err
             .to_string()
-            .contains("Signature not found for platform windows-x86_64"));
+            .contains("app_1.2.3_x64_en-US.msi (platform windows-x86_64)"));
         std::fs::remove_dir_all(&dir).ok();
     }
 
-    #[cfg(not(feature = "verify-signature"))]
     #[test]
     fn test_generate_latest_json_public_api_uses_cwd_for_version_and_output() {
         let _cwd_guard = CWD_LOCK.lock().unwrap_or_else(|poison| poison.into_inner());
@@ -801,6 +4709,7 @@ mod tests {
                 "unused-public-key",
                 "https://example.com/downloads",
                 "public-api notes",
+                Path::new("latest.json"),
             )
             .unwrap();
         }
@@ -816,10 +4725,10 @@ mod tests {
         std::fs::remove_dir_all(&dir).ok();
     }
 
-    #[cfg(not(feature = "verify-signature"))]
     #[test]
     fn test_generate_latest_json_auto_detects_paths_and_reads_pubkey() {
         let _cwd_guard = CWD_LOCK.lock().unwrap_or_else(|poison| poison.into_inner());
+        let _target_dir_guard = EnvVarGuard::remove("CARGO_TARGET_DIR");
         let dir = make_temp_dir();
         let bundle_dir = dir.join("target").join("release").join("bundle");
         write_file(
@@ -828,7 +4737,7 @@ mod tests {
         );
         write_file(
             &dir.join("tauri.conf.json"),
-            r#"{"plugins":{"updater":{"pubkey":"test-pubkey"}}}"#,
+            &format!(r#"{{"plugins":{{"updater":{{"pubkey":"{TEST_PUBKEY}"}}}}}}"#),
         );
         write_file(&bundle_dir.join("app_2.0.0_arm64.dmg"), "mac installer");
         write_file(&bundle_dir.join("app_2.0.0_arm64.dmg.sig"), "mac-signature");
@@ -847,4 +4756,42 @@ mod tests {
         );
         std::fs::remove_dir_all(&dir).ok();
     }
+
+    #[cfg(feature = "verify-signature")]
+    #[test]
+    fn test_generate_latest_json_verifies_signatures_against_public_key() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("bundle");
+        write_file(
+            &bundle_dir.join("app_1.2.3_x64_en-US.msi"),
+            "windows installer",
+        );
+        let (public_key, sig) = signature::tests::tauri_sign(b"windows installer");
+        write_file(&bundle_dir.join("app_1.2.3_x64_en-US.msi.sig"), &sig);
+        let (other_key, _) = signature::tests::tauri_sign(b"windows installer");
+        let options = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir(&bundle_dir)
+            .version("1.2.3")
+            .output(dir.join("latest.json"));
+
+        options
+            .clone()
+            .public_key(&public_key)
+            .verify(VerifyMode::Full)
+            .generate()
+            .unwrap();
+        let err = options
+            .clone()
+            .public_key(&other_key)
+            .verify(VerifyMode::Full)
+            .generate()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            LatestJsonError::SignatureVerificationFailed { .. }
+        ));
+        // Presence-only stays the default with the feature on.
+        options.public_key(&other_key).generate().unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
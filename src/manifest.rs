@@ -0,0 +1,251 @@
+//! Typed model of the updater manifest.
+
+use crate::{clock, error::WithPath, LatestJsonError, ValidationError};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// The `latest.json` document read by the Tauri updater.
+///
+/// Unknown fields are kept in `extra`, so a manifest can be deserialized,
+/// edited, and written back without losing data.
+///
+/// ```
+/// let manifest: tauri_latest_json::LatestJson = serde_json::from_str(
+///     r#"{"version":"1.0.0","pub_date":"2024-05-01T12:30:00Z","platforms":{}}"#,
+/// )?;
+/// assert_eq!(manifest.notes, "");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatestJson {
+    pub version: String,
+    #[serde(default)]
+    pub notes: String,
+    pub pub_date: String,
+    pub platforms: BTreeMap<String, PlatformEntry>,
+    /// Marks a security release (see `GenerateOptions::critical`). Written
+    /// only when true; the client decides how to force the update.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub critical: bool,
+    /// Clients below this version must update (see
+    /// `GenerateOptions::minimum_version`). Enforced by the client.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_version: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl LatestJson {
+    /// Reads and parses the manifest at `path`. Fields this crate does not
+    /// model, at the top level or in a platform entry, land in `extra` and
+    /// are written back unchanged.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, LatestJsonError> {
+        let path = path.as_ref();
+        serde_json::from_slice(&fs::read(path).with_path(path)?).with_path(path)
+    }
+}
+
+/// One downloadable artifact in [`LatestJson::platforms`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlatformEntry {
+    pub signature: String,
+    pub url: String,
+    /// Platform-specific notes (see `GenerateOptions::platform_notes`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Hex SHA-256 of the installer (see `GenerateOptions::include_checksums`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// Installer size in bytes (see `GenerateOptions::include_size`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One installer found by [`inspect_bundle`](crate::inspect_bundle).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallerInfo {
+    pub path: PathBuf,
+    /// The platform key the file name maps to, or `unknown`.
+    pub platform_key: String,
+    /// The `.sig` next to the installer, if there is one.
+    pub signature_path: Option<PathBuf>,
+}
+
+/// One installer a generation run discovered, in
+/// [`GenerationSummary::installers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallerStatus {
+    pub path: PathBuf,
+    /// The platform entry it became, or `None` when it was not published
+    /// (another installer won its platform, it was unsigned, or no platform
+    /// matched; see the summary's warnings).
+    pub platform_key: Option<String>,
+    /// Size in bytes, when the filesystem reports one.
+    pub size: Option<u64>,
+    pub signature: SignatureStatus,
+}
+
+/// Whether an installer had a signature, in [`InstallerStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Published, and the signature was checked against the public key.
+    Verified,
+    /// A signature exists but was not checked (presence-only or off mode,
+    /// or the installer was not published).
+    Present,
+    /// No signature file or `TAURI_SIG_<PLATFORM>` value.
+    Missing,
+}
+
+/// What a generation run produced, for logging and assertions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationSummary {
+    /// The version written to the manifest.
+    pub version: String,
+    /// The project file the version was read from; `None` when it was
+    /// given explicitly or taken from a git tag.
+    pub version_file: Option<PathBuf>,
+    /// Platform keys in the manifest, sorted.
+    pub platform_keys: Vec<String>,
+    /// Installers discovered in the bundle, including ones not used.
+    pub installer_count: usize,
+    /// What happened to each discovered installer, in path order.
+    pub installers: Vec<InstallerStatus>,
+    /// File names of installers skipped because they had no `.sig`.
+    pub unsigned: Vec<String>,
+    /// Soft problems worth showing the user; none of them stopped the run.
+    pub warnings: Vec<Warning>,
+}
+
+/// Checks that `latest` is what the Tauri updater accepts: `version` is
+/// semver, `pub_date` is RFC 3339, and there is at least one platform, each
+/// with a non-empty `signature` and `url`. Generation runs this on its
+/// output unless [`GenerateOptions::validate`](crate::GenerateOptions::validate)
+/// is turned off.
+pub fn validate_manifest(latest: &LatestJson) -> Result<(), ValidationError> {
+    validate_manifest_with(latest, ManifestChecks::default())
+}
+
+/// Which [`validate_manifest`] checks run; generation leaves out the ones
+/// the caller opted out of (non-semver versions, custom date layouts,
+/// unsigned entries).
+pub(crate) struct ManifestChecks {
+    pub(crate) version: bool,
+    pub(crate) pub_date: bool,
+    pub(crate) signatures: bool,
+}
+
+impl Default for ManifestChecks {
+    fn default() -> Self {
+        Self {
+            version: true,
+            pub_date: true,
+            signatures: true,
+        }
+    }
+}
+
+pub(crate) fn validate_manifest_with(
+    latest: &LatestJson,
+    checks: ManifestChecks,
+) -> Result<(), ValidationError> {
+    if checks.version && semver::Version::parse(&latest.version).is_err() {
+        return Err(ValidationError::InvalidVersion(latest.version.clone()));
+    }
+    if checks.pub_date && !clock::is_rfc3339(&latest.pub_date) {
+        return Err(ValidationError::InvalidPubDate(latest.pub_date.clone()));
+    }
+    if latest.platforms.is_empty() {
+        return Err(ValidationError::NoPlatforms);
+    }
+    for (platform, entry) in &latest.platforms {
+        if checks.signatures && entry.signature.trim().is_empty() {
+            return Err(ValidationError::EmptySignature {
+                platform: platform.clone(),
+            });
+        }
+        if entry.url.trim().is_empty() {
+            return Err(ValidationError::EmptyUrl {
+                platform: platform.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A problem that did not stop generation, collected in
+/// [`GenerationSummary::warnings`] rather than printed. Installer names are
+/// file names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// `installer` has no `.sig` and was left out (expected for `.dmg`, and
+    /// for any installer with [`VerifyMode::Off`](crate::VerifyMode::Off)).
+    UnsignedInstaller { platform: String, installer: String },
+    /// No platform key could be derived from `installer`'s name.
+    UnknownPlatform { installer: String },
+    /// Several installers for `platform` were equally preferred; `selected`
+    /// won by path order.
+    DuplicatePlatform {
+        platform: String,
+        selected: String,
+        skipped: Vec<String>,
+    },
+    /// `installer` is from another build and was dropped by
+    /// `GenerateOptions::version_filter`.
+    VersionMismatch { installer: String, version: String },
+    /// `signature` names no file next to it (the installer it signs is
+    /// `signature` minus its extension), e.g. left over from another build.
+    OrphanSignature { signature: String },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsignedInstaller {
+                platform,
+                installer,
+            } => {
+                let reason = if installer.ends_with(".dmg") {
+                    "Tauri does not sign .dmg files"
+                } else {
+                    "no signature file next to it"
+                };
+                write!(
+                    f,
+                    "skipped unsigned installer {} for {} ({})",
+                    installer, platform, reason
+                )
+            }
+            Self::UnknownPlatform { installer } => {
+                write!(f, "skipped {}: no platform matches its name", installer)
+            }
+            Self::DuplicatePlatform {
+                platform,
+                selected,
+                skipped,
+            } => write!(
+                f,
+                "{} has several equally preferred installers; selected {}, skipped {}",
+                platform,
+                selected,
+                skipped.join(", ")
+            ),
+            Self::VersionMismatch { installer, version } => write!(
+                f,
+                "skipped {}: its version does not match {}",
+                installer, version
+            ),
+            Self::OrphanSignature { signature } => {
+                write!(f, "{} matches no installer next to it", signature)
+            }
+        }
+    }
+}
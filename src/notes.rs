@@ -0,0 +1,99 @@
+//! Release notes from a literal string, a file, or a changelog section.
+
+use crate::{error::WithPath, GenerateOptions, LatestJsonError, Result};
+use std::fs;
+
+/// Picks the notes for `version` from whichever single source is set.
+pub(crate) fn resolve_notes(options: &GenerateOptions, version: &str) -> Result<String> {
+    let sources = [
+        !options.notes.is_empty(),
+        options.notes_file.is_some(),
+        options.notes_changelog.is_some(),
+    ];
+    if sources.iter().filter(|set| **set).count() > 1 {
+        return Err(LatestJsonError::ConflictingNotes);
+    }
+    if let Some(path) = &options.notes_file {
+        log::debug!("reading notes from {}", path.display());
+        return Ok(fs::read_to_string(path).with_path(path)?.trim().to_string());
+    }
+    if let Some(path) = &options.notes_changelog {
+        log::debug!("reading notes for {} from {}", version, path.display());
+        let changelog = fs::read_to_string(path).with_path(path)?;
+        return changelog_section(&changelog, version).ok_or_else(|| {
+            LatestJsonError::ChangelogSectionMissing {
+                path: path.clone(),
+                version: version.to_string(),
+            }
+        });
+    }
+    Ok(options.notes.clone())
+}
+
+/// Returns the body of the Keep a Changelog section for `version`: the
+/// lines after `## [1.2.0] - 2024-05-01` (brackets, date, and a leading `v`
+/// optional) up to the next `## ` heading, trimmed.
+pub(crate) fn changelog_section(changelog: &str, version: &str) -> Option<String> {
+    let mut lines = changelog.lines();
+    lines.find(|line| heading_version(line) == Some(version))?;
+    let body: Vec<&str> = lines.take_while(|line| !line.starts_with("## ")).collect();
+    Some(body.join("\n").trim().to_string())
+}
+
+fn heading_version(line: &str) -> Option<&str> {
+    let title = line.strip_prefix("## ")?.trim_start();
+    let title = title.strip_prefix('[').unwrap_or(title);
+    let end = title
+        .find(|c: char| c == ']' || c.is_whitespace())
+        .unwrap_or(title.len());
+    let version = &title[..end];
+    Some(version.strip_prefix('v').unwrap_or(version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{make_temp_dir, write_file};
+
+    const CHANGELOG: &str = "# Changelog\n\n## [Unreleased]\n\n- Next\n\n## [1.2.0] - 2024-05-01\n\n### Fixed\n\n- Crash on start\n\n## v1.1.0\n\n- Old\n";
+
+    #[test]
+    fn test_changelog_section_extracts_version_body() {
+        assert_eq!(
+            changelog_section(CHANGELOG, "1.2.0").as_deref(),
+            Some("### Fixed\n\n- Crash on start")
+        );
+        assert_eq!(
+            changelog_section(CHANGELOG, "1.1.0").as_deref(),
+            Some("- Old")
+        );
+        assert_eq!(changelog_section(CHANGELOG, "1.0.0"), None);
+    }
+
+    #[test]
+    fn test_resolve_notes_reads_one_source_and_rejects_conflicts() {
+        let dir = make_temp_dir();
+        let notes_path = dir.join("NOTES.md");
+        let changelog_path = dir.join("CHANGELOG.md");
+        write_file(&notes_path, "Multi\n\nparagraph\n");
+        write_file(&changelog_path, CHANGELOG);
+        let options = GenerateOptions::new("https://example.com");
+
+        let from_file = options.clone().notes_from_file(&notes_path);
+        assert_eq!(
+            resolve_notes(&from_file, "1.2.0").unwrap(),
+            "Multi\n\nparagraph"
+        );
+        let from_changelog = options.clone().notes_from_changelog(&changelog_path);
+        assert_eq!(resolve_notes(&from_changelog, "1.1.0").unwrap(), "- Old");
+        assert!(matches!(
+            resolve_notes(&from_changelog, "9.9.9"),
+            Err(LatestJsonError::ChangelogSectionMissing { .. })
+        ));
+        assert!(matches!(
+            resolve_notes(&from_file.notes("literal"), "1.2.0"),
+            Err(LatestJsonError::ConflictingNotes)
+        ));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
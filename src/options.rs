@@ -0,0 +1,727 @@
+//! Builder-style configuration for manifest generation.
+
+use crate::clock::{Clock, PubDateFormat, SystemClock, Timestamp};
+use crate::crypto::{SharedVerifier, Verifier};
+use crate::filesystem::{FileSystem, RealFs, WalkOptions};
+use crate::progress::{ProgressEvent, SharedProgress};
+use crate::{
+    build_latest_json_with_summary, channel_output_path, detect_bundle_dir, resolve_public_key,
+    validate_public_key, write_latest_json_with_options, GenerationSummary, LatestJson,
+    ManifestFormat, PackageJsonLookup, Result, VerifyMode, VersionSource, WindowsInstaller,
+    DEFAULT_SIGNATURE_EXTENSION,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Configuration for generating `latest.json`.
+///
+/// Start from [`GenerateOptions::new`] with the download URL base, chain the
+/// setters you need, and call [`GenerateOptions::generate`]. Anything left
+/// unset is auto-detected the same way the CLI does it.
+///
+/// ```no_run
+/// let manifest = tauri_latest_json::GenerateOptions::new("https://example.com/downloads")
+///     .notes("Bug fixes")
+///     .output("dist/latest.json")
+///     .version("1.2.0+build.7")
+///     .generate()?;
+/// println!("{} platforms", manifest.platforms.len());
+/// # Ok::<(), tauri_latest_json::LatestJsonError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GenerateOptions {
+    pub(crate) download_url_base: String,
+    pub(crate) notes: String,
+    pub(crate) notes_file: Option<PathBuf>,
+    pub(crate) notes_changelog: Option<PathBuf>,
+    pub(crate) output: Option<OutputTarget>,
+    pub(crate) output_mode: OutputMode,
+    pub(crate) channel_aware: bool,
+    pub(crate) channel_outputs: HashMap<String, PathBuf>,
+    pub(crate) bundle_dir: Option<PathBuf>,
+    pub(crate) profile: Option<String>,
+    pub(crate) installer_extensions: Option<Vec<String>>,
+    pub(crate) app_name: Option<String>,
+    pub(crate) signature_extension: String,
+    pub(crate) windows_installer: WindowsInstaller,
+    pub(crate) manifest_format: ManifestFormat,
+    pub(crate) verify: VerifyMode,
+    pub(crate) walk: WalkOptions,
+    pub(crate) read_retries: u32,
+    pub(crate) strict_platforms: bool,
+    pub(crate) public_keys: Option<Vec<String>>,
+    pub(crate) public_key_path: Option<PathBuf>,
+    pub(crate) tauri_conf_path: Option<PathBuf>,
+    pub(crate) version: Option<String>,
+    pub(crate) version_source: VersionSource,
+    pub(crate) package_json_lookup: PackageJsonLookup,
+    pub(crate) version_filter: bool,
+    pub(crate) critical: bool,
+    pub(crate) minimum_version: Option<String>,
+    pub(crate) strict_semver: bool,
+    pub(crate) merge_into_existing: bool,
+    pub(crate) platform_notes: HashMap<String, String>,
+    pub(crate) url_overrides: HashMap<String, String>,
+    pub(crate) clock: SharedClock,
+    pub(crate) pub_date_format: PubDateFormat,
+    pub(crate) validate: bool,
+    pub(crate) filesystem: SharedFs,
+    pub(crate) progress: Option<SharedProgress>,
+    pub(crate) verifier: Option<SharedVerifier>,
+    pub(crate) dry_run: bool,
+    pub(crate) git_fallback: bool,
+    pub(crate) offline: bool,
+    pub(crate) require_all_signed: bool,
+    pub(crate) include_checksums: bool,
+    pub(crate) include_size: bool,
+    pub(crate) allow_file_urls: bool,
+    pub(crate) preserve_relative_path: bool,
+    pub(crate) pretty: bool,
+    pub(crate) backup: bool,
+    pub(crate) overwrite: bool,
+    pub(crate) expect_platforms: Vec<String>,
+    pub(crate) allow_missing_platforms: bool,
+}
+
+/// Keeps `GenerateOptions` cloneable and debuggable with any clock.
+#[derive(Clone)]
+pub(crate) struct SharedClock(Arc<dyn Clock + Send + Sync>);
+
+impl SharedClock {
+    pub(crate) fn now(&self) -> Timestamp {
+        self.0.now()
+    }
+}
+
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Clock")
+    }
+}
+
+/// Keeps `GenerateOptions` cloneable and debuggable with any filesystem.
+#[derive(Clone)]
+pub(crate) struct SharedFs(Arc<dyn FileSystem>);
+
+impl SharedFs {
+    pub(crate) fn as_ref(&self) -> &dyn FileSystem {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for SharedFs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FileSystem")
+    }
+}
+
+impl GenerateOptions {
+    /// Creates options for installers hosted under `download_url_base`, with
+    /// every other knob at its default.
+    pub fn new(download_url_base: impl Into<String>) -> Self {
+        Self {
+            download_url_base: download_url_base.into(),
+            notes: String::new(),
+            notes_file: None,
+            notes_changelog: None,
+            output: None,
+            output_mode: OutputMode::Single,
+            channel_aware: false,
+            channel_outputs: HashMap::new(),
+            bundle_dir: None,
+            profile: None,
+            installer_extensions: None,
+            app_name: None,
+            signature_extension: DEFAULT_SIGNATURE_EXTENSION.to_string(),
+            windows_installer: WindowsInstaller::Msi,
+            manifest_format: ManifestFormat::V2,
+            verify: VerifyMode::default(),
+            walk: WalkOptions::default(),
+            read_retries: 0,
+            strict_platforms: false,
+            public_keys: None,
+            public_key_path: None,
+            tauri_conf_path: None,
+            version: None,
+            version_filter: false,
+            critical: false,
+            minimum_version: None,
+            version_source: VersionSource::Auto,
+            package_json_lookup: PackageJsonLookup::Local,
+            strict_semver: true,
+            merge_into_existing: false,
+            platform_notes: HashMap::new(),
+            url_overrides: HashMap::new(),
+            clock: SharedClock(Arc::new(SystemClock)),
+            pub_date_format: PubDateFormat::default(),
+            validate: true,
+            filesystem: SharedFs(Arc::new(RealFs)),
+            progress: None,
+            verifier: None,
+            dry_run: false,
+            git_fallback: false,
+            offline: false,
+            require_all_signed: false,
+            include_checksums: false,
+            include_size: false,
+            allow_file_urls: false,
+            preserve_relative_path: false,
+            pretty: true,
+            backup: false,
+            overwrite: true,
+            expect_platforms: Vec::new(),
+            allow_missing_platforms: false,
+        }
+    }
+
+    /// Sets the release notes (empty by default).
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = notes.into();
+        self
+    }
+
+    /// Reads the release notes from `path` (trimmed). Setting this together
+    /// with literal [`notes`](Self::notes) or a changelog is an error.
+    pub fn notes_from_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.notes_file = Some(path.into());
+        self
+    }
+
+    /// Uses the section for the published version from the Keep a Changelog
+    /// file at `path` (e.g. `## [1.2.0] - 2024-05-01`) as the release notes.
+    /// Setting this together with another notes source is an error.
+    pub fn notes_from_changelog(mut self, path: impl Into<PathBuf>) -> Self {
+        self.notes_changelog = Some(path.into());
+        self
+    }
+
+    /// Writes the manifest to `output` instead of `./latest.json`. Missing
+    /// parent directories are created.
+    pub fn output(mut self, output: impl Into<PathBuf>) -> Self {
+        self.output = Some(OutputTarget::File(output.into()));
+        self
+    }
+
+    /// Chooses where the manifest goes; [`OutputTarget::Stdout`] streams it
+    /// into a pipeline without a temp file. Setting [`output`](Self::output)
+    /// is the same as [`OutputTarget::File`].
+    pub fn output_target(mut self, target: OutputTarget) -> Self {
+        self.output = Some(target);
+        self
+    }
+
+    /// Writes one manifest per platform next to the output instead of the
+    /// output itself (see [`OutputMode::PerPlatform`]).
+    /// [`merge_into_existing`](Self::merge_into_existing) is ignored in that
+    /// mode; a backup is kept of each file replaced.
+    pub fn output_mode(mut self, mode: OutputMode) -> Self {
+        self.output_mode = mode;
+        self
+    }
+
+    /// Writes pre-release versions to a per-channel manifest next to the
+    /// output: `1.2.0-beta.3` goes to `latest-beta.json`, while stable
+    /// versions keep writing `latest.json`. The channel is the first
+    /// pre-release identifier.
+    pub fn channel_aware(mut self, channel_aware: bool) -> Self {
+        self.channel_aware = channel_aware;
+        self
+    }
+
+    /// Writes `channel` releases to `output` instead of the derived
+    /// `latest-<channel>.json`. Implies [`channel_aware`](Self::channel_aware).
+    pub fn channel_output(
+        mut self,
+        channel: impl Into<String>,
+        output: impl Into<PathBuf>,
+    ) -> Self {
+        self.channel_outputs.insert(channel.into(), output.into());
+        self.channel_aware = true;
+        self
+    }
+
+    /// Reads installers from `bundle_dir` instead of detecting it.
+    pub fn bundle_dir(mut self, bundle_dir: impl Into<PathBuf>) -> Self {
+        self.bundle_dir = Some(bundle_dir.into());
+        self
+    }
+
+    /// Detects the bundle under `target/<profile>/bundle` only, e.g. `debug`
+    /// after `tauri build --debug`. Without this `release` is tried, then
+    /// `debug`. Ignored when [`bundle_dir`](Self::bundle_dir) is set.
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Only treats files with these extensions as installers, e.g.
+    /// `["msi", "app.tar.gz", "AppImage"]` to leave `.deb`/`.rpm` out of the
+    /// manifest. Defaults to [`DEFAULT_INSTALLER_EXTENSIONS`](crate::DEFAULT_INSTALLER_EXTENSIONS).
+    pub fn installer_extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.installer_extensions = Some(extensions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only uses installers of the product `app_name`, for bundle trees shared
+    /// by several apps: the file name must start with the name (ignoring
+    /// ASCII case) followed by `_` or `.`, as in `MyApp_1.0.0_x64_en-US.msi`
+    /// and `MyApp.app.tar.gz`.
+    pub fn app_name(mut self, app_name: impl Into<String>) -> Self {
+        self.app_name = Some(app_name.into());
+        self
+    }
+
+    /// The extension of signature files (default `sig`, leading `.`
+    /// optional), for signing flows that write e.g. `.minisig`. The
+    /// installer is found by stripping it from the file name, so it may
+    /// contain dots: with `minisig.sig`, `app.msi.minisig.sig` signs `app.msi`.
+    pub fn signature_extension(mut self, extension: impl Into<String>) -> Self {
+        let extension = extension.into();
+        self.signature_extension = extension.trim_start_matches('.').to_string();
+        self
+    }
+
+    /// How deep installer and signature discovery looks below the bundle
+    /// directory (default 4; files directly in it are at depth 1). Hidden
+    /// directories are always skipped.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.walk.max_depth = depth;
+        self
+    }
+
+    /// Follows symlinks during installer and signature discovery (default
+    /// `false`, which skips them). Files reachable through several links
+    /// are listed once either way.
+    pub fn follow_symlinks(mut self, follow: bool) -> Self {
+        self.walk.follow_symlinks = follow;
+        self
+    }
+
+    /// Retries installer and signature reads that fail with a transient
+    /// error (timeout, interruption, `EIO`) up to `retries` times, waiting
+    /// 100 ms, then 200 ms, and so on, for bundles on a flaky network mount.
+    /// `NotFound` and other errors fail at once. Default 0.
+    pub fn read_retries(mut self, retries: u32) -> Self {
+        self.read_retries = retries;
+        self
+    }
+
+    /// Fails with [`LatestJsonError::UnknownPlatform`](crate::LatestJsonError::UnknownPlatform)
+    /// when an installer's name maps to no platform, instead of skipping it
+    /// with a [`Warning::UnknownPlatform`](crate::Warning::UnknownPlatform).
+    pub fn strict_platforms(mut self, strict: bool) -> Self {
+        self.strict_platforms = strict;
+        self
+    }
+
+    /// Chooses the MSI or the NSIS `.exe` as the Windows updater entry when
+    /// the bundle has both (default [`WindowsInstaller::Msi`]).
+    pub fn windows_installer_preference(mut self, preference: WindowsInstaller) -> Self {
+        self.windows_installer = preference;
+        self
+    }
+
+    /// Targets the Tauri 1 updater (or `v1Compatible` artifacts) instead of
+    /// the default [`ManifestFormat::V2`]: entries point at the `.msi.zip`,
+    /// `.nsis.zip`, `.AppImage.tar.gz`, and `.app.tar.gz` archives. Ignored
+    /// when [`installer_extensions`](Self::installer_extensions) is set.
+    pub fn manifest_format(mut self, format: ManifestFormat) -> Self {
+        self.manifest_format = format;
+        self
+    }
+
+    /// Checks the generated manifest with
+    /// [`validate_manifest`](crate::validate_manifest) before returning or
+    /// writing it (default `true`). The `version` check is skipped when
+    /// [`strict_semver`](Self::strict_semver) is off, and the `pub_date`
+    /// check for [`PubDateFormat::Custom`] layouts; empty signatures pass
+    /// with [`VerifyMode::Off`].
+    pub fn validate(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Chooses whether signatures are verified, only required to be present,
+    /// or not required (default [`VerifyMode::PresenceOnly`]; choose
+    /// [`VerifyMode::Full`] to check signatures against the public key).
+    pub fn verify(mut self, mode: VerifyMode) -> Self {
+        self.verify = mode;
+        self
+    }
+
+    /// Uses `public_key` instead of `TAURI_SIGNING_PUBLIC_KEY` or the config.
+    /// Unlike a resolved key it is used as given; call
+    /// [`validate_public_key`](crate::validate_public_key) to check it up front.
+    pub fn public_key(mut self, public_key: impl Into<String>) -> Self {
+        self.public_keys = Some(vec![public_key.into()]);
+        self
+    }
+
+    /// Like [`public_key`](Self::public_key) with several keys: a signature
+    /// passes when any of them verifies it, so installers signed with the
+    /// old or the new key are both accepted during a rotation.
+    pub fn public_keys<I, S>(mut self, public_keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.public_keys = Some(public_keys.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Reads the public key from a `.pub` file at `path` (e.g.
+    /// `~/.tauri/myapp.key.pub`). Takes precedence over the environment and
+    /// the config, but not over [`public_key`](Self::public_key).
+    pub fn public_key_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.public_key_path = Some(path.into());
+        self
+    }
+
+    /// Reads the updater config from `path` instead of searching for it.
+    pub fn tauri_conf_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tauri_conf_path = Some(path.into());
+        self
+    }
+
+    /// Uses `version` in the manifest instead of reading it from
+    /// `package.json`, `tauri.conf.json`, or `Cargo.toml`.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Chooses which project file is read first for the version (default
+    /// [`VersionSource::Auto`]). Ignored when [`version`](Self::version) is set.
+    pub fn version_source(mut self, source: VersionSource) -> Self {
+        self.version_source = source;
+        self
+    }
+
+    /// Chooses which `package.json` the version comes from (default
+    /// [`PackageJsonLookup::Local`]), e.g. the workspace root's in a pnpm
+    /// monorepo. [`GenerationSummary::version_file`] names the file used.
+    pub fn package_json_lookup(mut self, lookup: PackageJsonLookup) -> Self {
+        self.package_json_lookup = lookup;
+        self
+    }
+
+    /// When the bundle holds installers from more than one version (judged
+    /// by the `X.Y.Z` in their file names), skips the ones that do not match
+    /// the published version instead of failing with
+    /// [`LatestJsonError::MixedVersions`](crate::LatestJsonError::MixedVersions).
+    pub fn version_filter(mut self, filter: bool) -> Self {
+        self.version_filter = filter;
+        self
+    }
+
+    /// Writes `"critical": true` to the manifest for security releases.
+    /// The Tauri updater ignores unknown fields, so the app's update check
+    /// has to read this and act on it.
+    pub fn critical(mut self, critical: bool) -> Self {
+        self.critical = critical;
+        self
+    }
+
+    /// Writes `"minimum_version"`: clients older than this should be
+    /// forced to update. Must be semver no greater than the published
+    /// version. Like [`critical`](Self::critical), the client enforces it.
+    pub fn minimum_version(mut self, version: impl Into<String>) -> Self {
+        self.minimum_version = Some(version.into());
+        self
+    }
+
+    /// Requires the version to be valid semver (default `true`). A leading
+    /// `v` is stripped either way; disable this for non-semver schemes.
+    pub fn strict_semver(mut self, strict: bool) -> Self {
+        self.strict_semver = strict;
+        self
+    }
+
+    /// Falls back to `git describe --tags --abbrev=0` when no project file
+    /// has a version. git only runs when the other sources come up empty.
+    pub fn git_fallback(mut self, git_fallback: bool) -> Self {
+        self.git_fallback = git_fallback;
+        self
+    }
+
+    /// Guarantees generation and signature verification stay on the local
+    /// filesystem, for air-gapped CI. The crate makes no network requests
+    /// in any mode (verification is local minisign crypto); the one process
+    /// it can spawn is git for [`git_fallback`](Self::git_fallback), which
+    /// offline mode skips.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Fails when any discovered installer has no signature, including ones
+    /// that lost platform selection (e.g. an NSIS `.exe` next to a signed
+    /// `.msi`), `.dmg` files, and installers that [`VerifyMode::Off`] would
+    /// skip. Only a selected installer can use `TAURI_SIG_<PLATFORM>`.
+    /// Every unsigned installer is listed in one
+    /// [`LatestJsonError::UnsignedInstallers`](crate::LatestJsonError::UnsignedInstallers)
+    /// instead of stopping at the first.
+    pub fn require_all_signed(mut self, require_all_signed: bool) -> Self {
+        self.require_all_signed = require_all_signed;
+        self
+    }
+
+    /// Merges into the manifest already at the output path instead of
+    /// overwriting it. Platforms of the same version are combined; an older
+    /// version is kept in a `versions` array, newest first. Ignored with
+    /// [`OutputMode::PerPlatform`], whose files are always written fresh.
+    pub fn merge_into_existing(mut self, merge: bool) -> Self {
+        self.merge_into_existing = merge;
+        self
+    }
+
+    /// Adds a `notes` field to each platform entry, keyed by platform
+    /// (e.g. `darwin-aarch64`). Platforms without an entry get the global
+    /// notes; the top-level `notes` is always written.
+    pub fn platform_notes(mut self, notes: HashMap<String, String>) -> Self {
+        self.platform_notes = notes;
+        self
+    }
+
+    /// Uses these URLs verbatim for the keyed platforms (e.g. a notarized
+    /// mirror for `darwin-aarch64`) instead of joining the download URL
+    /// base. Each must be an absolute `http(s)` URL.
+    pub fn url_overrides(mut self, urls: HashMap<String, String>) -> Self {
+        self.url_overrides = urls;
+        self
+    }
+
+    /// Reads `pub_date` from `clock` instead of the system time.
+    pub fn clock(mut self, clock: impl Clock + Send + Sync + 'static) -> Self {
+        self.clock = SharedClock(Arc::new(clock));
+        self
+    }
+
+    /// Writes `pub_date` as the given instant, e.g. when re-publishing.
+    pub fn pub_date(self, pub_date: Timestamp) -> Self {
+        self.clock(pub_date)
+    }
+
+    /// Writes `pub_date` in `format` instead of RFC 3339 with whole seconds,
+    /// e.g. [`PubDateFormat::Rfc3339Millis`] for servers that expect
+    /// milliseconds. An invalid custom layout fails generation.
+    pub fn pub_date_format(mut self, format: PubDateFormat) -> Self {
+        self.pub_date_format = format;
+        self
+    }
+
+    /// Reads installers and signatures and writes the manifest through
+    /// `filesystem` instead of the real one, e.g. a [`MockFs`](crate::MockFs)
+    /// in tests. Version and config discovery still use the real filesystem.
+    pub fn filesystem(mut self, filesystem: impl FileSystem + 'static) -> Self {
+        self.filesystem = SharedFs(Arc::new(filesystem));
+        self
+    }
+
+    /// Checks signatures with `verifier` instead of minisign in
+    /// [`VerifyMode::Full`], which this makes usable without the
+    /// `verify-signature` feature. Each installer is read whole and passed
+    /// to [`Verifier::verify`](crate::Verifier::verify) once per public key.
+    pub fn verifier(mut self, verifier: impl Verifier + 'static) -> Self {
+        self.verifier = Some(SharedVerifier::new(verifier));
+        self
+    }
+
+    /// Calls `progress` as each selected installer is checked, e.g. to drive
+    /// a progress bar on large releases. The crate renders nothing itself.
+    /// Installers run in parallel; calls are serialized, never concurrent.
+    pub fn progress(mut self, progress: impl FnMut(ProgressEvent) + Send + 'static) -> Self {
+        self.progress = Some(SharedProgress::new(progress));
+        self
+    }
+
+    /// Adds a hex `sha256` of each installer to its platform entry. Files
+    /// are hashed in streaming fashion, so large bundles are fine.
+    pub fn include_checksums(mut self, include: bool) -> Self {
+        self.include_checksums = include;
+        self
+    }
+
+    /// Adds each installer's `size` in bytes to its platform entry, e.g. for
+    /// a download page. Read from file metadata, so it costs no extra pass.
+    pub fn include_size(mut self, include: bool) -> Self {
+        self.include_size = include;
+        self
+    }
+
+    /// Accepts a `file://` download URL base, for local updater testing.
+    /// Without this only absolute `http(s)` URLs are allowed.
+    pub fn allow_file_urls(mut self, allow: bool) -> Self {
+        self.allow_file_urls = allow;
+        self
+    }
+
+    /// Puts each installer's path relative to the bundle directory in its
+    /// URL (`nsis/App.exe` instead of `App.exe`), for hosts that mirror the
+    /// bundle tree. Segments are joined with `/` on every OS.
+    pub fn preserve_relative_path(mut self, preserve: bool) -> Self {
+        self.preserve_relative_path = preserve;
+        self
+    }
+
+    /// Copies an existing manifest at the output path to `<output>.bak`
+    /// (e.g. `latest.json.bak`) before replacing it, so the previous release
+    /// can be restored in one step. Each run overwrites the last backup. With
+    /// [`OutputMode::PerPlatform`], every per-platform file is backed up the
+    /// same way.
+    pub fn backup(mut self, backup: bool) -> Self {
+        self.backup = backup;
+        self
+    }
+
+    /// With `false`, fails with
+    /// [`LatestJsonError::OutputExists`](crate::LatestJsonError::OutputExists)
+    /// before writing anything if a target file already exists (every
+    /// per-platform file is checked), e.g. as a safety net for local runs.
+    /// Merging replaces the file too, so it fails the same way. Defaults to
+    /// `true`; dry runs and stdout output are unaffected.
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Fails with [`LatestJsonError::MissingExpectedPlatform`](crate::LatestJsonError::MissingExpectedPlatform)
+    /// when any of these platform keys is absent from the final manifest, so
+    /// a failed build job cannot silently ship a partial release.
+    pub fn expect_platforms<I, S>(mut self, platforms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.expect_platforms = platforms.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Logs a warning instead of failing when an
+    /// [`expect_platforms`](Self::expect_platforms) key is missing.
+    pub fn allow_missing_platforms(mut self, allow: bool) -> Self {
+        self.allow_missing_platforms = allow;
+        self
+    }
+
+    /// Writes indented JSON (default `true`). Pass `false` for compact
+    /// output when serving the manifest to many clients.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Runs detection, signature checks, and merging as usual but logs the
+    /// manifest at `info` level instead of writing it. Errors still surface.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Builds the manifest, writes it (unless [`dry_run`](Self::dry_run)),
+    /// and returns what was written.
+    pub fn generate(&self) -> Result<LatestJson> {
+        self.generate_with_summary().map(|(manifest, _)| manifest)
+    }
+
+    /// Like [`generate`](Self::generate), also returning a
+    /// [`GenerationSummary`] of the run.
+    pub fn generate_with_summary(&self) -> Result<(LatestJson, GenerationSummary)> {
+        let (latest_json, summary, output) = self.build()?;
+        let latest_json = write_latest_json_with_options(latest_json, &output, self)?;
+        Ok((latest_json, summary))
+    }
+
+    /// Resolves everything left unset and builds the manifest, returning it
+    /// with the output path it should be written to.
+    pub(crate) fn build(&self) -> Result<(LatestJson, GenerationSummary, PathBuf)> {
+        let bundle_dir = match &self.bundle_dir {
+            Some(dir) => dir.clone(),
+            None => detect_bundle_dir(self.profile.as_deref())?,
+        };
+        let public_keys = match &self.public_keys {
+            Some(keys) => keys.clone(),
+            None => {
+                let keys = resolve_public_key(
+                    self.public_key_path.as_deref(),
+                    self.tauri_conf_path.as_deref(),
+                )?;
+                for key in &keys {
+                    validate_public_key(key)?;
+                }
+                keys
+            }
+        };
+        let project_dir = std::env::current_dir()?;
+        let output = self.output_or_default(&project_dir);
+        let (latest_json, summary) = build_latest_json_with_summary(
+            &bundle_dir,
+            &public_keys,
+            &project_dir,
+            self,
+            &mut |_| None,
+        )?;
+        let output = match self.channel_aware {
+            true => channel_output_path(&output, &latest_json.version, &self.channel_outputs),
+            false => output,
+        };
+        Ok((latest_json, summary, output))
+    }
+
+    /// Replaces the URL and notes, for the positional free functions.
+    pub(crate) fn with_url_and_notes(mut self, download_url_base: &str, notes: &str) -> Self {
+        self.download_url_base = download_url_base.to_string();
+        self.notes = notes.to_string();
+        self
+    }
+
+    /// The output path, defaulting to `latest.json` in `project_dir`.
+    fn output_or_default(&self, project_dir: &Path) -> PathBuf {
+        match &self.output {
+            Some(OutputTarget::File(path)) => path.clone(),
+            _ => project_dir.join("latest.json"),
+        }
+    }
+
+    pub(crate) fn emit_progress(&self, event: ProgressEvent) {
+        if let Some(progress) = &self.progress {
+            progress.emit(event);
+        }
+    }
+
+    pub(crate) fn writes_to_stdout(&self) -> bool {
+        matches!(self.output, Some(OutputTarget::Stdout))
+    }
+}
+
+/// Where [`GenerateOptions::generate`] writes the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputTarget {
+    /// A file, replaced atomically.
+    File(PathBuf),
+    /// Standard output, byte-for-byte what the file would hold. Merging,
+    /// backups, and channel paths do not apply; logs go through `log` as
+    /// always, so stdout carries only the manifest.
+    Stdout,
+}
+
+/// Whether [`GenerateOptions::generate`] writes one manifest or one per
+/// platform.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// One manifest with every platform.
+    #[default]
+    Single,
+    /// One manifest per platform in the output's directory, named by
+    /// `file_name` (see [`split_manifest`](crate::split_manifest) for the
+    /// placeholders), e.g. [`DEFAULT_PER_PLATFORM_FILE_NAME`](crate::DEFAULT_PER_PLATFORM_FILE_NAME).
+    PerPlatform { file_name: String },
+}
@@ -0,0 +1,52 @@
+//! Progress events for callers that render a progress bar or structured
+//! logs while installers are checked and hashed.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// A step of generation, passed to [`GenerateOptions::progress`](crate::GenerateOptions::progress).
+///
+/// Installers are processed in parallel, so events for different installers
+/// interleave; `Finished` always comes last.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProgressEvent {
+    /// Work on the selected installer `name` (a file name) began.
+    StartedInstaller { name: String },
+    /// `name`'s signature was checked against the public key (and, when
+    /// requested, the file was hashed and sized); its platform entry is
+    /// ready. Only sent with [`VerifyMode::Full`](crate::VerifyMode::Full).
+    Verified { name: String },
+    /// Like [`Verified`](Self::Verified), but verification was skipped
+    /// ([`VerifyMode::PresenceOnly`](crate::VerifyMode::PresenceOnly) or
+    /// [`VerifyMode::Off`](crate::VerifyMode::Off)), so the signature was
+    /// not checked.
+    Processed { name: String },
+    /// Every selected installer was processed; `count` platforms are in the
+    /// manifest.
+    Finished { count: usize },
+}
+
+/// Keeps `GenerateOptions` cloneable and debuggable with any callback.
+#[derive(Clone)]
+pub(crate) struct SharedProgress(Arc<Mutex<dyn FnMut(ProgressEvent) + Send>>);
+
+impl SharedProgress {
+    pub(crate) fn new(progress: impl FnMut(ProgressEvent) + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(progress)))
+    }
+
+    pub(crate) fn emit(&self, event: ProgressEvent) {
+        // A callback that panicked once still gets later events.
+        let mut progress = self.0.lock().unwrap_or_else(|poison| poison.into_inner());
+        progress(event);
+    }
+}
+
+impl fmt::Debug for SharedProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Progress")
+    }
+}
@@ -0,0 +1,97 @@
+//! Machine-readable report of a generation run, e.g. for a release
+//! dashboard that would otherwise scrape the log.
+
+use crate::{FileSystem, GenerationSummary, RealFs, Result, SignatureStatus};
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Describes the run behind `summary` as JSON: the version and the file it
+/// was read from (`null` when given explicitly or taken from a git tag),
+/// the platforms written, each discovered installer with its platform
+/// (`null` when not published), size, and signature status (`verified`,
+/// `present`, or `missing`), and the warnings as messages.
+pub fn run_report(summary: &GenerationSummary) -> Value {
+    let installers: Vec<Value> = summary
+        .installers
+        .iter()
+        .map(|installer| {
+            json!({
+                "path": installer.path,
+                "platform": installer.platform_key,
+                "size": installer.size,
+                "signature": match installer.signature {
+                    SignatureStatus::Verified => "verified",
+                    SignatureStatus::Present => "present",
+                    SignatureStatus::Missing => "missing",
+                },
+            })
+        })
+        .collect();
+    let warnings: Vec<String> = summary.warnings.iter().map(ToString::to_string).collect();
+    json!({
+        "version": summary.version,
+        "version_file": summary.version_file,
+        "platforms": summary.platform_keys,
+        "installers": installers,
+        "warnings": warnings,
+    })
+}
+
+/// Writes [`run_report`] to `path` (pretty-printed, parent directories
+/// created).
+pub fn write_report(summary: &GenerationSummary, path: &Path) -> Result<()> {
+    write_report_with(&RealFs, summary, path)
+}
+
+pub(crate) fn write_report_with(
+    fs: &dyn FileSystem,
+    summary: &GenerationSummary,
+    path: &Path,
+) -> Result<()> {
+    let report = serde_json::to_string_pretty(&run_report(summary))?;
+    fs.write(path, report.as_bytes())?;
+    log::info!("run report written to {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GenerateOptions, MockFs, VerifyMode};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_write_report_describes_installers_and_warnings() {
+        let mock = MockFs::new()
+            .with_file("/bundle/app_1.0.0_amd64.AppImage", "appimage")
+            .with_file("/bundle/app_1.0.0_amd64.AppImage.sig", "sig")
+            .with_file("/bundle/app_1.0.0_amd64.deb", "deb")
+            .with_file("/bundle/app_1.0.0_x64.dmg", "dmg");
+        let (_, summary) = GenerateOptions::new("https://example.com/downloads")
+            .bundle_dir("/bundle")
+            .public_key("pubkey")
+            .version("1.0.0")
+            .output("/out/latest.json")
+            .verify(VerifyMode::PresenceOnly)
+            .filesystem(mock.clone())
+            .generate_with_summary()
+            .unwrap();
+
+        write_report_with(&mock, &summary, Path::new("/out/report.json")).unwrap();
+
+        let report: Value =
+            serde_json::from_slice(&mock.get(Path::new("/out/report.json")).unwrap()).unwrap();
+        assert_eq!(report["version"], "1.0.0");
+        assert_eq!(report["version_file"], Value::Null);
+        assert_eq!(report["platforms"], json!(["linux-x86_64"]));
+        assert_eq!(
+            report["installers"],
+            json!([
+                { "path": PathBuf::from("/bundle/app_1.0.0_amd64.AppImage"), "platform": "linux-x86_64", "size": 8, "signature": "present" },
+                { "path": PathBuf::from("/bundle/app_1.0.0_amd64.deb"), "platform": null, "size": 3, "signature": "missing" },
+                { "path": PathBuf::from("/bundle/app_1.0.0_x64.dmg"), "platform": null, "size": 3, "signature": "missing" },
+            ])
+        );
+        assert_eq!(report["warnings"], json!([summary.warnings[0].to_string()]));
+    }
+}
@@ -0,0 +1,231 @@
+//! Minisign signing of updater artifacts.
+//!
+//! Produces `.sig` files in the same format as `tauri signer sign`, so a
+//! detached signing step can run separately from the build machine.
+
+use crate::{
+    error::WithPath, sign_installers_with, LatestJsonError, Result, SignError, Signer,
+    DEFAULT_SIGNATURE_EXTENSION,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use minisign::{SecretKey, SecretKeyBox};
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use zeroize::Zeroizing;
+
+/// Environment variable holding the private key (content or a path to it).
+pub const PRIVATE_KEY_ENV: &str = "TAURI_SIGNING_PRIVATE_KEY";
+
+/// Environment variable holding the private key password.
+pub const PRIVATE_KEY_PASSWORD_ENV: &str = "TAURI_SIGNING_PRIVATE_KEY_PASSWORD";
+
+/// Signs every installer in `bundle_dir` that has no adjacent `.sig`.
+///
+/// `private_key` and `password` fall back to [`PRIVATE_KEY_ENV`] and
+/// [`PRIVATE_KEY_PASSWORD_ENV`]. The key may be the base64 blob printed by
+/// `tauri signer generate`, the raw key file content, or a path to the file.
+/// Encrypted keys are decrypted with `password`; see [`MinisignSigner`] for
+/// how the decrypted key is wiped.
+/// `.dmg` files are skipped because the updater never downloads them.
+/// Returns the paths of the `.sig` files written. Use
+/// [`sign_installers_with`] for another [`Signer`] or signature extension.
+pub fn sign_installers(
+    bundle_dir: &Path,
+    private_key: Option<&str>,
+    password: Option<&str>,
+) -> Result<Vec<PathBuf>> {
+    sign_installers_with(
+        bundle_dir,
+        &MinisignSigner::new(private_key, password)?,
+        DEFAULT_SIGNATURE_EXTENSION,
+    )
+}
+
+/// The default [`Signer`]: minisign with a Tauri private key, producing the
+/// same `.sig` content as `tauri signer sign`.
+///
+/// The decrypted key is kept as bytes that are zeroed when the signer is
+/// dropped, and parsed into a minisign key for each signature. minisign does
+/// not wipe its own key type, so those short-lived copies (and the one
+/// produced while decrypting) are freed without being zeroed.
+pub struct MinisignSigner {
+    secret_key: Zeroizing<Vec<u8>>,
+}
+
+impl MinisignSigner {
+    /// Decodes and decrypts the key; see [`sign_installers`] for the accepted
+    /// forms and the environment fallbacks.
+    pub fn new(private_key: Option<&str>, password: Option<&str>) -> Result<Self> {
+        let private_key = Zeroizing::new(match private_key {
+            Some(key) => key.to_string(),
+            None => env_value(PRIVATE_KEY_ENV).ok_or(LatestJsonError::PrivateKeyMissing)?,
+        });
+        let password = Zeroizing::new(match password {
+            Some(password) => password.to_string(),
+            None => env_value(PRIVATE_KEY_PASSWORD_ENV).unwrap_or_default(),
+        });
+        Ok(Self {
+            secret_key: decode_secret_key(&private_key, password)?,
+        })
+    }
+
+    fn sign_with_comment(
+        &self,
+        data: &mut dyn Read,
+        trusted_comment: &str,
+    ) -> std::result::Result<String, SignError> {
+        let secret_key =
+            SecretKey::from_bytes(&self.secret_key).map_err(|e| SignError::new(e.to_string()))?;
+        let signature = minisign::sign(
+            None,
+            &secret_key,
+            data,
+            Some(trusted_comment),
+            Some("signature from tauri secret key"),
+        )
+        .map_err(|e| SignError::new(e.to_string()))?;
+        Ok(STANDARD.encode(signature.to_string()))
+    }
+}
+
+impl Signer for MinisignSigner {
+    fn sign(&self, data: &mut dyn Read) -> std::result::Result<String, SignError> {
+        self.sign_with_comment(data, &format!("timestamp:{}", timestamp()))
+    }
+
+    fn sign_file(
+        &self,
+        installer_name: &str,
+        data: &mut dyn Read,
+    ) -> std::result::Result<String, SignError> {
+        let trusted_comment = format!("timestamp:{}\tfile:{}", timestamp(), installer_name);
+        self.sign_with_comment(data, &trusted_comment)
+    }
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+fn decode_secret_key(
+    private_key: &str,
+    mut password: Zeroizing<String>,
+) -> Result<Zeroizing<Vec<u8>>> {
+    let private_key = private_key.trim();
+    let text = Zeroizing::new(if Path::new(private_key).is_file() {
+        fs::read_to_string(private_key).with_path(Path::new(private_key))?
+    } else {
+        private_key.to_string()
+    });
+    let text = Zeroizing::new(
+        STANDARD
+            .decode(text.trim())
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .unwrap_or_else(|| text.trim().to_string()),
+    );
+
+    let key_box = SecretKeyBox::from_string(&text)
+        .map_err(|e| LatestJsonError::InvalidPrivateKey(e.to_string()))?;
+    // Always pass a password: `None` would make minisign prompt on the terminal.
+    SecretKey::from_box(key_box, Some(std::mem::take(&mut *password)))
+        .map(|secret_key| Zeroizing::new(secret_key.to_bytes()))
+        .map_err(|e| {
+            // minisign reports a checksum mismatch after decryption this way.
+            if e.to_string() == "Wrong password for that key" {
+                LatestJsonError::InvalidKeyPassword
+            } else {
+                LatestJsonError::InvalidPrivateKey(e.to_string())
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{make_temp_dir, write_file};
+    use minisign::{KeyPair, PublicKey, SignatureBox};
+    use std::fs::File;
+
+    /// Returns a public key and the matching `tauri signer generate` style key.
+    fn tauri_private_key(password: &str) -> (PublicKey, String) {
+        let KeyPair { pk, sk } =
+            KeyPair::generate_encrypted_keypair(Some(password.to_string())).unwrap();
+        let key = STANDARD.encode(sk.to_box(None).unwrap().to_string());
+        (pk, key)
+    }
+
+    #[test]
+    fn test_sign_installers_writes_verifiable_tauri_signatures() {
+        let dir = make_temp_dir();
+        let installer = dir.join("app_1.0.0_amd64.AppImage");
+        write_file(&installer, "linux installer");
+        write_file(&dir.join("app_1.0.0_x64.dmg"), "dmg");
+        write_file(&dir.join("app_1.0.0_x64_en-US.msi"), "msi");
+        write_file(&dir.join("app_1.0.0_x64_en-US.msi.sig"), "existing");
+        let (pk, private_key) = tauri_private_key("");
+
+        let written = sign_installers(&dir, Some(&private_key), Some("")).unwrap();
+
+        assert_eq!(written, vec![dir.join("app_1.0.0_amd64.AppImage.sig")]);
+        let encoded = fs::read_to_string(&written[0]).unwrap();
+        let signature = SignatureBox::from_string(
+            &String::from_utf8(STANDARD.decode(encoded).unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert!(signature
+            .trusted_comment()
+            .unwrap()
+            .ends_with("file:app_1.0.0_amd64.AppImage"));
+        minisign::verify(
+            &pk,
+            &signature,
+            File::open(&installer).unwrap(),
+            true,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("app_1.0.0_x64_en-US.msi.sig")).unwrap(),
+            "existing"
+        );
+        assert!(!dir.join("app_1.0.0_x64.dmg.sig").exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sign_installers_decrypts_password_protected_key() {
+        let dir = make_temp_dir();
+        write_file(&dir.join("app_1.0.0_x64_en-US.msi"), "msi");
+        let (_, private_key) = tauri_private_key("hunter2");
+
+        let err = sign_installers(&dir, Some(&private_key), Some("wrong")).unwrap_err();
+        assert!(matches!(err, LatestJsonError::InvalidKeyPassword));
+        assert!(!dir.join("app_1.0.0_x64_en-US.msi.sig").exists());
+
+        let written = sign_installers(&dir, Some(&private_key), Some("hunter2")).unwrap();
+        assert_eq!(written.len(), 1);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sign_installers_rejects_malformed_key() {
+        let dir = make_temp_dir();
+        write_file(&dir.join("app_1.0.0_amd64.AppImage"), "linux installer");
+
+        let err = sign_installers(&dir, Some("not a key"), Some("")).unwrap_err();
+        assert!(matches!(err, LatestJsonError::InvalidPrivateKey(_)));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
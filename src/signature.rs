@@ -0,0 +1,305 @@
+//! Minisign signature verification for updater artifacts.
+//!
+//! Tauri stores both the updater public key and each `.sig` file as base64
+//! over the textual minisign format, so both are decoded before verifying.
+
+use crate::{
+    crypto::verify_any_key, error::WithPath, FileSystem, LatestJson, LatestJsonError, RealFs,
+    Result, Verifier, VerifyError, WalkOptions,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use minisign_verify::{PublicKey, Signature};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// A platform in an existing manifest that does not match the bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationFailure {
+    /// Platform key, e.g. `windows-x86_64`.
+    pub platform: String,
+    /// The local installer, when one was found.
+    pub installer: Option<PathBuf>,
+    /// Why the entry failed.
+    pub reason: String,
+}
+
+/// Checks every platform in the manifest at `path` against `bundle_dir`.
+///
+/// Each entry's installer is located by the file name at the end of its
+/// `url`, and the entry's `signature` is verified against that file with
+/// `public_key`. Returns one [`VerificationFailure`] per bad entry; an empty
+/// list means the manifest matches the bundle. This catches a signature
+/// that was regenerated without regenerating the manifest.
+pub fn verify_latest_json(
+    path: &Path,
+    bundle_dir: &Path,
+    public_key: &str,
+) -> Result<Vec<VerificationFailure>> {
+    verify_latest_json_with_keys(path, bundle_dir, &[public_key.to_string()])
+}
+
+/// Like [`verify_latest_json`], but an entry passes when any of
+/// `public_keys` verifies it, e.g. the old and new key during a rotation.
+pub fn verify_latest_json_with_keys(
+    path: &Path,
+    bundle_dir: &Path,
+    public_keys: &[String],
+) -> Result<Vec<VerificationFailure>> {
+    let manifest = LatestJson::from_file(path)?;
+
+    let mut files = HashMap::new();
+    for path in RealFs.walk(bundle_dir, WalkOptions::default())? {
+        if let Some(name) = path.file_name() {
+            let name = name.to_string_lossy().into_owned();
+            files.entry(name).or_insert(path);
+        }
+    }
+
+    let mut failures = Vec::new();
+    for (platform, entry) in &manifest.platforms {
+        let encoded_name = entry.url.rsplit('/').next().unwrap_or_default();
+        let file_name = percent_encoding::percent_decode_str(encoded_name).decode_utf8_lossy();
+        let Some(installer) = files.get(file_name.as_ref()) else {
+            failures.push(VerificationFailure {
+                platform: platform.clone(),
+                installer: None,
+                reason: format!(
+                    "installer {} not found in {}",
+                    file_name,
+                    bundle_dir.display()
+                ),
+            });
+            continue;
+        };
+        if let Err(e) = verify_signature(&RealFs, installer, &entry.signature, public_keys) {
+            let reason = match e {
+                LatestJsonError::SignatureVerificationFailed { reason, .. } => reason,
+                e => e.to_string(),
+            };
+            failures.push(VerificationFailure {
+                platform: platform.clone(),
+                installer: Some(installer.clone()),
+                reason,
+            });
+        }
+    }
+    Ok(failures)
+}
+
+/// Verifies `signature` (the `.sig` content) for `installer`, passing when
+/// any of `public_keys` verifies it.
+pub(crate) fn verify_signature(
+    filesystem: &dyn FileSystem,
+    installer: &Path,
+    signature: &str,
+    public_keys: &[String],
+) -> Result<()> {
+    verify_any_key(installer, public_keys, |public_key| {
+        verify_signature_with_key(filesystem, installer, signature, public_key)
+    })
+}
+
+/// The default [`Verifier`]: minisign, with keys and signatures in Tauri's
+/// base64-wrapped form (bare `RW...` keys are accepted too). Generation
+/// streams installers through the same check instead of reading them whole.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinisignVerifier;
+
+impl Verifier for MinisignVerifier {
+    fn verify(
+        &self,
+        data: &[u8],
+        signature: &str,
+        public_key: &str,
+    ) -> std::result::Result<(), VerifyError> {
+        let public_key = decode_public_key(public_key).map_err(VerifyError::new)?;
+        let signature = decode_signature(signature).map_err(VerifyError::new)?;
+        public_key
+            .verify(data, &signature, true)
+            .map_err(|e| VerifyError::new(e.to_string()))
+    }
+}
+
+fn verify_signature_with_key(
+    filesystem: &dyn FileSystem,
+    installer: &Path,
+    signature: &str,
+    public_key: &str,
+) -> Result<()> {
+    let fail = |reason: String| LatestJsonError::SignatureVerificationFailed {
+        installer: installer.to_path_buf(),
+        reason,
+    };
+
+    let public_key = decode_public_key(public_key).map_err(fail)?;
+    let signature = decode_signature(signature).map_err(fail)?;
+
+    match public_key.verify_stream(&signature) {
+        Ok(mut verifier) => {
+            let mut file = filesystem.open(installer).with_path(installer)?;
+            let mut buf = vec![0u8; 64 * 1024];
+            loop {
+                let n = file.read(&mut buf).with_path(installer)?;
+                if n == 0 {
+                    break;
+                }
+                verifier.update(&buf[..n]);
+            }
+            verifier.finalize()
+        }
+        // Legacy (non-prehashed) signatures cannot be streamed.
+        Err(minisign_verify::Error::UnsupportedLegacyMode) => {
+            let data = filesystem.read(installer).with_path(installer)?;
+            public_key.verify(&data, &signature, true)
+        }
+        Err(e) => Err(e),
+    }
+    .map_err(|e| fail(e.to_string()))
+}
+
+/// Accepts either the base64-wrapped `minisign.pub` text Tauri writes to
+/// `tauri.conf.json` or the bare `RW...` key line.
+fn decode_public_key(public_key: &str) -> std::result::Result<PublicKey, String> {
+    let public_key = public_key.trim();
+    if let Some(text) = decode_base64_text(public_key) {
+        if let Ok(key) = PublicKey::decode(&text) {
+            return Ok(key);
+        }
+    }
+    PublicKey::from_base64(public_key).map_err(|e| format!("invalid public key: {}", e))
+}
+
+fn decode_signature(signature: &str) -> std::result::Result<Signature, String> {
+    let signature = signature.trim();
+    let text = decode_base64_text(signature).unwrap_or_else(|| signature.to_string());
+    Signature::decode(&text).map_err(|e| format!("invalid signature: {}", e))
+}
+
+fn decode_base64_text(value: &str) -> Option<String> {
+    let bytes = STANDARD.decode(value).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::tests::{make_temp_dir, write_file};
+    use minisign::KeyPair;
+    use std::io::Cursor;
+
+    /// Returns a Tauri-style `(public_key, signature)` pair for `data`.
+    pub(crate) fn tauri_sign(data: &[u8]) -> (String, String) {
+        let KeyPair { pk, sk } = KeyPair::generate_unencrypted_keypair().unwrap();
+        let public_key = STANDARD.encode(pk.to_box().unwrap().to_string());
+        let signature = minisign::sign(Some(&pk), &sk, Cursor::new(data), None, None).unwrap();
+        (public_key, STANDARD.encode(signature.to_string()))
+    }
+
+    #[test]
+    fn test_verify_signature_accepts_matching_key() {
+        let dir = make_temp_dir();
+        let installer = dir.join("app_1.0.0_x64_en-US.msi");
+        write_file(&installer, "windows installer");
+        let (public_key, signature) = tauri_sign(b"windows installer");
+
+        verify_signature(&RealFs, &installer, &signature, &[public_key]).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_signature_accepts_any_rotation_key() {
+        let dir = make_temp_dir();
+        let installer = dir.join("app_1.0.0_x64_en-US.msi");
+        write_file(&installer, "windows installer");
+        let (old_key, signature) = tauri_sign(b"windows installer");
+        let (new_key, _) = tauri_sign(b"windows installer");
+
+        verify_signature(&RealFs, &installer, &signature, &[new_key.clone(), old_key]).unwrap();
+        let err = verify_signature(&RealFs, &installer, &signature, &[new_key]).unwrap_err();
+        assert!(matches!(
+            err,
+            LatestJsonError::SignatureVerificationFailed { .. }
+        ));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_minisign_verifier_checks_data_in_memory() {
+        let (public_key, signature) = tauri_sign(b"windows installer");
+
+        MinisignVerifier
+            .verify(b"windows installer", &signature, &public_key)
+            .unwrap();
+        assert!(MinisignVerifier
+            .verify(b"tampered installer", &signature, &public_key)
+            .is_err());
+        assert!(MinisignVerifier
+            .verify(b"windows installer", "not a signature", &public_key)
+            .unwrap_err()
+            .reason
+            .starts_with("invalid signature"));
+    }
+
+    #[test]
+    fn test_verify_latest_json_reports_stale_and_missing_entries() {
+        let dir = make_temp_dir();
+        let bundle_dir = dir.join("bundle");
+        write_file(
+            &bundle_dir.join("app_1.0.0_x64_en-US.msi"),
+            "windows installer",
+        );
+        write_file(
+            &bundle_dir.join("app_1.0.0_amd64.AppImage"),
+            "rebuilt installer",
+        );
+        let (public_key, msi_signature) = tauri_sign(b"windows installer");
+        let (_, stale_signature) = tauri_sign(b"linux installer");
+        let manifest = dir.join("latest.json");
+        write_file(
+            &manifest,
+            &serde_json::json!({
+                "version": "1.0.0",
+                "pub_date": "2024-05-01T12:30:00Z",
+                "platforms": {
+                    "windows-x86_64": { "signature": msi_signature, "url": "https://cdn/app_1.0.0_x64_en-US.msi" },
+                    "linux-x86_64": { "signature": stale_signature, "url": "https://cdn/app_1.0.0_amd64.AppImage" },
+                    "darwin-aarch64": { "signature": "x", "url": "https://cdn/app.app.tar.gz" }
+                }
+            })
+            .to_string(),
+        );
+
+        let failures = verify_latest_json(&manifest, &bundle_dir, &public_key).unwrap();
+        let platforms: Vec<_> = failures.iter().map(|f| f.platform.as_str()).collect();
+        assert_eq!(platforms, ["darwin-aarch64", "linux-x86_64"]);
+        assert!(failures[0].installer.is_none());
+        assert_eq!(
+            failures[1].installer.as_deref(),
+            Some(bundle_dir.join("app_1.0.0_amd64.AppImage").as_path())
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_other_key_and_tampered_data() {
+        let dir = make_temp_dir();
+        let installer = dir.join("app_1.0.0_x64_en-US.msi");
+        write_file(&installer, "tampered installer");
+        let (public_key, signature) = tauri_sign(b"windows installer");
+        let (other_key, _) = tauri_sign(b"windows installer");
+
+        let err = verify_signature(&RealFs, &installer, &signature, &[public_key]).unwrap_err();
+        assert!(matches!(
+            err,
+            LatestJsonError::SignatureVerificationFailed { .. }
+        ));
+        let err = verify_signature(&RealFs, &installer, &signature, &[other_key]).unwrap_err();
+        assert!(matches!(
+            err,
+            LatestJsonError::SignatureVerificationFailed { .. }
+        ));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
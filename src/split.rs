@@ -0,0 +1,71 @@
+//! One manifest per platform, for deployments that serve a file per target
+//! (e.g. `latest-windows.json`, `latest-darwin.json`) instead of one
+//! `latest.json` for every platform.
+
+use crate::LatestJson;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// The file name pattern [`OutputMode::PerPlatform`](crate::OutputMode::PerPlatform)
+/// uses unless given another: one file per platform key.
+pub const DEFAULT_PER_PLATFORM_FILE_NAME: &str = "latest-{{platform}}.json";
+
+/// Splits `latest` into one manifest per file name, keyed by that name.
+///
+/// `file_name` may use `{{platform}}` (the key, e.g. `windows-x86_64`),
+/// `{{os}}` (`windows`), and `{{arch}}` (`x86_64`). Each manifest keeps
+/// the shared `version`, `notes`, `pub_date`, and other top-level fields
+/// (except the merge history in `versions`) and holds the platforms whose
+/// key renders to its name, so `latest-{{os}}.json` puts both Windows
+/// arches in `latest-windows.json`. (`{{os}}` is not `--url`'s `{{target}}`,
+/// which stands for the whole platform key.)
+///
+/// ```
+/// use tauri_latest_json::{split_manifest, LatestJson};
+///
+/// let latest: LatestJson = serde_json::from_str(r#"{
+///     "version": "1.0.0", "pub_date": "2024-05-01T12:30:00Z",
+///     "platforms": {
+///         "darwin-aarch64": { "signature": "a", "url": "https://cdn/app.app.tar.gz" },
+///         "windows-x86_64": { "signature": "b", "url": "https://cdn/app.msi" }
+///     }
+/// }"#)?;
+/// let files = split_manifest(&latest, "latest-{{os}}.json");
+/// assert_eq!(files["latest-windows.json"].platforms.len(), 1);
+/// assert_eq!(files["latest-darwin.json"].version, "1.0.0");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub fn split_manifest(latest: &LatestJson, file_name: &str) -> BTreeMap<String, LatestJson> {
+    let mut shared = latest.clone();
+    shared.platforms.clear();
+    shared.extra.remove("versions");
+    let mut files: BTreeMap<String, LatestJson> = BTreeMap::new();
+    for (platform, entry) in &latest.platforms {
+        let (os, arch) = platform.split_once('-').unwrap_or((platform, ""));
+        let name = file_name
+            .replace("{{platform}}", platform)
+            .replace("{{os}}", os)
+            .replace("{{arch}}", arch);
+        files
+            .entry(name)
+            .or_insert_with(|| shared.clone())
+            .platforms
+            .insert(platform.clone(), entry.clone());
+    }
+    files
+}
+
+/// [`split_manifest`] with each file placed next to `output_path`.
+pub(crate) fn per_platform_outputs(
+    latest: &LatestJson,
+    output_path: &Path,
+    file_name: &str,
+) -> Vec<(PathBuf, LatestJson)> {
+    let dir = output_path.parent().unwrap_or_else(|| Path::new(""));
+    split_manifest(latest, file_name)
+        .into_iter()
+        .map(|(name, manifest)| (dir.join(name), manifest))
+        .collect()
+}
@@ -0,0 +1,228 @@
+//! Typed updater platforms.
+//!
+//! A [`Target`] pairs an [`Os`] with an [`Arch`]; [`Target::key`] spells the
+//! `latest.json` platform key, so the naming lives in one place.
+
+use std::fmt;
+
+/// Operating system part of a platform key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Os {
+    Windows,
+    Darwin,
+    Linux,
+}
+
+impl Os {
+    /// The name used in platform keys, e.g. `darwin`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Windows => "windows",
+            Self::Darwin => "darwin",
+            Self::Linux => "linux",
+        }
+    }
+}
+
+/// CPU architecture part of a platform key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Arch {
+    X86_64,
+    Aarch64,
+    Armv7,
+    /// A macOS universal binary (both Intel and Apple silicon).
+    Universal,
+    I686,
+}
+
+impl Arch {
+    /// The name used in platform keys, e.g. `aarch64`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::X86_64 => "x86_64",
+            Self::Aarch64 => "aarch64",
+            Self::Armv7 => "armv7",
+            Self::Universal => "universal",
+            Self::I686 => "i686",
+        }
+    }
+}
+
+/// An updater platform such as `darwin-aarch64`.
+///
+/// ```
+/// use tauri_latest_json::{Arch, Os, Target};
+///
+/// let target = Target::new(Os::Darwin, Arch::Aarch64);
+/// assert_eq!(target.key(), "darwin-aarch64");
+/// assert_eq!(Target::from_key("darwin-aarch64"), Some(target));
+/// assert_eq!(Target::from_key("darwin-arm64"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Target {
+    pub os: Os,
+    pub arch: Arch,
+}
+
+impl Target {
+    pub const fn new(os: Os, arch: Arch) -> Self {
+        Self { os, arch }
+    }
+
+    /// The `latest.json` platform key, `{os}-{arch}`.
+    pub fn key(&self) -> String {
+        self.to_string()
+    }
+
+    /// Parses a platform key; `None` for anything [`key`](Self::key) would
+    /// not produce.
+    pub fn from_key(key: &str) -> Option<Self> {
+        const OSES: [Os; 3] = [Os::Windows, Os::Darwin, Os::Linux];
+        const ARCHES: [Arch; 5] = [
+            Arch::X86_64,
+            Arch::Aarch64,
+            Arch::Armv7,
+            Arch::Universal,
+            Arch::I686,
+        ];
+        let (os, arch) = key.split_once('-')?;
+        Some(Self::new(
+            OSES.into_iter().find(|o| o.as_str() == os)?,
+            ARCHES.into_iter().find(|a| a.as_str() == arch)?,
+        ))
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.os.as_str(), self.arch.as_str())
+    }
+}
+
+/// Rust target triples Tauri may embed in artifact names, with the updater
+/// platform each one maps to.
+const TARGET_TRIPLES: &[(&str, Target)] = &[
+    (
+        "aarch64-apple-darwin",
+        Target::new(Os::Darwin, Arch::Aarch64),
+    ),
+    ("x86_64-apple-darwin", Target::new(Os::Darwin, Arch::X86_64)),
+    (
+        "universal-apple-darwin",
+        Target::new(Os::Darwin, Arch::Universal),
+    ),
+    (
+        "x86_64-pc-windows-msvc",
+        Target::new(Os::Windows, Arch::X86_64),
+    ),
+    (
+        "x86_64-pc-windows-gnu",
+        Target::new(Os::Windows, Arch::X86_64),
+    ),
+    (
+        "aarch64-pc-windows-msvc",
+        Target::new(Os::Windows, Arch::Aarch64),
+    ),
+    ("i686-pc-windows-msvc", Target::new(Os::Windows, Arch::I686)),
+    (
+        "x86_64-unknown-linux-gnu",
+        Target::new(Os::Linux, Arch::X86_64),
+    ),
+    (
+        "x86_64-unknown-linux-musl",
+        Target::new(Os::Linux, Arch::X86_64),
+    ),
+    (
+        "aarch64-unknown-linux-gnu",
+        Target::new(Os::Linux, Arch::Aarch64),
+    ),
+    (
+        "aarch64-unknown-linux-musl",
+        Target::new(Os::Linux, Arch::Aarch64),
+    ),
+    (
+        "armv7-unknown-linux-gnueabihf",
+        Target::new(Os::Linux, Arch::Armv7),
+    ),
+    ("i686-unknown-linux-gnu", Target::new(Os::Linux, Arch::I686)),
+];
+
+/// Maps an installer file name to its updater platform, e.g.
+/// `app_1.0.0_x64_en-US.msi` to `windows-x86_64`. Returns `None` for files
+/// that are not recognized installers.
+///
+/// ```
+/// let target = tauri_latest_json::detect_platform_key("app_1.0.0_aarch64.app.tar.gz");
+/// assert_eq!(target.map(|t| t.key()).as_deref(), Some("darwin-aarch64"));
+/// ```
+pub fn detect_platform_key(filename: &str) -> Option<Target> {
+    let lower = filename.to_ascii_lowercase();
+    // A full triple is unambiguous, so it wins over extension and arch tokens.
+    if let Some((_, target)) = TARGET_TRIPLES
+        .iter()
+        .find(|(triple, _)| lower.contains(triple))
+    {
+        return Some(*target);
+    }
+    let is_arm64 = || lower.contains("aarch64") || lower.contains("arm64");
+    let (os, arch) = if lower.ends_with(".msi")
+        || lower.ends_with(".exe")
+        || lower.ends_with(".msi.zip")
+        || lower.ends_with(".nsis.zip")
+    {
+        if has_arch_token(&lower, &["aarch64", "arm64"]) {
+            (Os::Windows, Arch::Aarch64)
+        } else if is_i686(&lower) {
+            (Os::Windows, Arch::I686)
+        } else {
+            (Os::Windows, Arch::X86_64)
+        }
+    } else if lower.ends_with(".app.tar.gz") || lower.ends_with(".dmg") {
+        // A universal bundle gets its own key rather than both arch keys.
+        if has_arch_token(&lower, &["universal"]) {
+            (Os::Darwin, Arch::Universal)
+        } else if is_arm64() {
+            (Os::Darwin, Arch::Aarch64)
+        } else {
+            (Os::Darwin, Arch::X86_64)
+        }
+    } else if lower.ends_with(".appimage")
+        || lower.ends_with(".deb")
+        || lower.ends_with(".rpm")
+        || lower.ends_with(".tar.gz")
+    {
+        if is_arm64() {
+            (Os::Linux, Arch::Aarch64)
+        } else if lower.contains("armhf") || lower.contains("armv7") {
+            (Os::Linux, Arch::Armv7)
+        } else if is_i686(&lower) {
+            (Os::Linux, Arch::I686)
+        } else {
+            (Os::Linux, Arch::X86_64)
+        }
+    } else {
+        return None;
+    };
+    Some(Target::new(os, arch))
+}
+
+/// Returns true for a 32-bit x86 build: an `i686`, `i386`, `x86`, or `ia32`
+/// token. Splitting on `_` turns `x86_64` into `x86` and `64`, so any
+/// `x86_64` in the name means a 64-bit build instead.
+fn is_i686(lower: &str) -> bool {
+    !lower.contains("x86_64") && has_arch_token(lower, &["i686", "i386", "x86", "ia32"])
+}
+
+/// Returns true when one of `arch_tokens` appears as a whole token after the
+/// product name, e.g. `arm64` in `MyApp_1.0.0_arm64_en-US.msi`.
+///
+/// The leading `_`-separated segment is the product name and is ignored so an
+/// app literally named "arm64" is not mistaken for an ARM build.
+fn has_arch_token(lower: &str, arch_tokens: &[&str]) -> bool {
+    let rest = match lower.split_once('_') {
+        Some((_, rest)) => rest,
+        None => lower,
+    };
+    rest.split(['_', '-', '.', ' '])
+        .any(|token| arch_tokens.contains(&token))
+}
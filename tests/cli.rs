@@ -2,6 +2,9 @@ use assert_cmd::Command;
 use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 
+/// A well-formed updater public key, as `tauri signer generate` prints it.
+const PUBKEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDA4MDcwNjA1MDQwMzAyMDEKUldRQkFnTUVCUVlIQ0FBQkFnTUVCUVlIQ0FrS0N3d05EZzhRRVJJVEZCVVdGeGdaR2hzY0hSNGYK";
+
 #[test]
 fn help_flag_prints_usage() {
     Command::cargo_bin("tauri-latest-json")
@@ -60,3 +63,231 @@ fn missing_notes_fails_without_tty_prompt() {
         .failure()
         .stderr(contains("notes").and(contains("not in a terminal")));
 }
+
+fn temp_project(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "tauri-latest-json-cli-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("target").join("release").join("bundle")).unwrap();
+    std::fs::write(
+        dir.join("package.json"),
+        r#"{"name":"dummy","version":"1.0.0"}"#,
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn generate_subcommand_writes_to_output_with_custom_config() {
+    let dir = temp_project("generate");
+    let bundle_dir = dir.join("target").join("release").join("bundle");
+    std::fs::write(bundle_dir.join("app_1.0.0_x64_en-US.msi"), "installer").unwrap();
+    std::fs::write(bundle_dir.join("app_1.0.0_x64_en-US.msi.sig"), "signature").unwrap();
+    std::fs::create_dir_all(dir.join("conf")).unwrap();
+    std::fs::write(
+        dir.join("conf").join("tauri.conf.json"),
+        format!(r#"{{"plugins":{{"updater":{{"pubkey":"{PUBKEY}"}}}}}}"#),
+    )
+    .unwrap();
+
+    Command::cargo_bin("tauri-latest-json")
+        .unwrap()
+        .current_dir(&dir)
+        .env_remove("CARGO_TARGET_DIR")
+        .env("GITHUB_OUTPUT", dir.join("github_output"))
+        .args([
+            "generate",
+            "--url",
+            "https://example.com/downloads",
+            "--notes",
+            "cli notes",
+            "--github-output",
+            "--output",
+            "manifest.json",
+            "--config",
+            "conf/tauri.conf.json",
+            "--app-version",
+            "1.0.0+ci.7",
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::is_empty())
+        .stderr(contains("matched platform windows-x86_64"));
+
+    let manifest = std::fs::read_to_string(dir.join("manifest.json")).unwrap();
+    assert!(manifest.contains("https://example.com/downloads/app_1.0.0_x64_en-US.msi"));
+    assert!(manifest.contains(r#""version": "1.0.0+ci.7""#));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("github_output")).unwrap(),
+        "version=1.0.0+ci.7\nplatforms=windows-x86_64\n"
+    );
+
+    Command::cargo_bin("tauri-latest-json")
+        .unwrap()
+        .args(["verify", dir.join("manifest.json").to_str().unwrap()])
+        .assert()
+        .success();
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn generate_subcommand_streams_manifest_to_stdout() {
+    let dir = temp_project("stdout");
+    let bundle_dir = dir.join("target").join("release").join("bundle");
+    std::fs::write(bundle_dir.join("app_1.0.0_amd64.AppImage"), "installer").unwrap();
+    std::fs::write(bundle_dir.join("app_1.0.0_amd64.AppImage.sig"), "signature").unwrap();
+    std::fs::write(
+        dir.join("tauri.conf.json"),
+        format!(r#"{{"plugins":{{"updater":{{"pubkey":"{PUBKEY}"}}}}}}"#),
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("tauri-latest-json")
+        .unwrap()
+        .current_dir(&dir)
+        .env_remove("CARGO_TARGET_DIR")
+        .args([
+            "generate",
+            "--url",
+            "https://example.com",
+            "--notes",
+            "n",
+            "--stdout",
+        ])
+        .assert()
+        .success()
+        .stderr(contains("matched platform linux-x86_64"))
+        .get_output()
+        .stdout
+        .clone();
+
+    let manifest: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        manifest["platforms"]["linux-x86_64"]["signature"],
+        "signature"
+    );
+    assert!(!dir.join("latest.json").exists());
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn generate_subcommand_exits_2_when_no_installers() {
+    let dir = temp_project("no-installers");
+    std::fs::write(
+        dir.join("tauri.conf.json"),
+        format!(r#"{{"plugins":{{"updater":{{"pubkey":"{PUBKEY}"}}}}}}"#),
+    )
+    .unwrap();
+
+    Command::cargo_bin("tauri-latest-json")
+        .unwrap()
+        .current_dir(&dir)
+        .env_remove("CARGO_TARGET_DIR")
+        .args(["generate", "--url", "https://example.com", "--notes", "n"])
+        .assert()
+        .code(2)
+        .stderr(contains("No installers found"));
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn verify_subcommand_rejects_manifest_without_platforms() {
+    let dir = temp_project("verify");
+    let path = dir.join("latest.json");
+    std::fs::write(
+        &path,
+        r#"{"version":"1.0.0","notes":"","pub_date":"2026-01-01T00:00:00Z","platforms":{}}"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("tauri-latest-json")
+        .unwrap()
+        .args(["verify", path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(contains("platforms"));
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[cfg(unix)]
+#[test]
+fn generate_subcommand_offline_spawns_no_processes() {
+    use std::net::TcpListener;
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = temp_project("offline");
+    let bundle_dir = dir.join("target").join("release").join("bundle");
+    std::fs::write(bundle_dir.join("app_1.0.0_amd64.AppImage"), "installer").unwrap();
+    std::fs::write(bundle_dir.join("app_1.0.0_amd64.AppImage.sig"), "signature").unwrap();
+    std::fs::write(
+        dir.join("tauri.conf.json"),
+        format!(r#"{{"plugins":{{"updater":{{"pubkey":"{PUBKEY}"}}}}}}"#),
+    )
+    .unwrap();
+    std::fs::remove_file(dir.join("package.json")).unwrap();
+    // A `git` that records being run; it is the only program on PATH, so
+    // the marker is written with a shell builtin.
+    let bin_dir = dir.join("bin");
+    let marker = dir.join("git-was-run");
+    std::fs::create_dir_all(&bin_dir).unwrap();
+    std::fs::write(
+        bin_dir.join("git"),
+        format!("#!/bin/sh\n: > '{}'\necho v1.0.0\n", marker.display()),
+    )
+    .unwrap();
+    std::fs::set_permissions(bin_dir.join("git"), std::fs::Permissions::from_mode(0o755)).unwrap();
+    // The download host and every proxy point at this listener, so any
+    // HTTP request the run makes shows up as an accepted connection.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let local = format!("http://{}", listener.local_addr().unwrap());
+    let generate = |extra: &[&str]| {
+        let mut command = Command::cargo_bin("tauri-latest-json").unwrap();
+        command
+            .current_dir(&dir)
+            .env_remove("CARGO_TARGET_DIR")
+            .env("PATH", &bin_dir)
+            .envs(
+                [
+                    "http_proxy",
+                    "https_proxy",
+                    "HTTP_PROXY",
+                    "HTTPS_PROXY",
+                    "ALL_PROXY",
+                ]
+                .map(|name| (name, &local)),
+            )
+            .args([
+                "generate",
+                "--url",
+                &local,
+                "--notes",
+                "n",
+                "--verify",
+                "presence",
+                "--git-fallback",
+            ])
+            .args(extra);
+        command
+    };
+
+    generate(&["--offline"])
+        .assert()
+        .failure()
+        .stderr(contains("offline mode does not run git"));
+    generate(&["--offline", "--app-version", "1.0.0"])
+        .assert()
+        .success();
+    assert!(!marker.exists());
+    assert!(dir.join("latest.json").exists());
+    // Without --offline the fake git runs, which shows the marker works.
+    generate(&[]).assert().success();
+    assert!(marker.exists());
+    assert!(listener
+        .accept()
+        .is_err_and(|e| e.kind() == std::io::ErrorKind::WouldBlock));
+    std::fs::remove_dir_all(&dir).ok();
+}